@@ -2,8 +2,11 @@
 
 pub mod app_mod {
     use std::{
-        io::{self, Stdout},
+        env,
+        io::{self, Stdout, Write},
+        path::PathBuf,
         process::Child,
+        sync::mpsc::{self, Receiver},
         time::Duration,
         vec,
     };
@@ -13,7 +16,7 @@ pub mod app_mod {
     use crossterm::{
         event::{
             self, poll, DisableBracketedPaste, DisableFocusChange, DisableMouseCapture,
-            EnableBracketedPaste, EnableFocusChange, EnableMouseCapture, Event, KeyCode,
+            EnableBracketedPaste, EnableFocusChange, EnableMouseCapture, Event, KeyCode, KeyEvent,
             KeyEventKind, KeyModifiers, KeyboardEnhancementFlags, PopKeyboardEnhancementFlags,
             PushKeyboardEnhancementFlags,
         },
@@ -25,12 +28,79 @@ pub mod app_mod {
 
     use crate::{
         lib::{
-            config::config::{read_rclone_config, ConfigStruct, DriveStruct},
-            mount::mount::{start_mounting, stop_mounting},
-            utils::utils::login_google_drive,
+            auth::auth::{
+                create_remote, create_with_token, is_headless, login_google_drive, validate_token,
+                AuthStatus, AuthStruct, HeadlessStruct,
+            },
+            browser::browser::{
+                breadcrumb, child_path, parent_path, sort_files, start_listing, start_listing_with,
+                FileStruct, ListingStruct, SortKey,
+            },
+            bwlimit::bwlimit::{apply_bwlimit, bwlimit_form, validate_bwlimit},
+            cache::cache::{purge_cache, start_cache_scan, CacheScan, CacheStruct},
+            commander::commander::{op_args, CommanderOp, PaneStruct},
+            composer::composer::{composer_form, create_composed},
+            config::config::{
+                read_rclone_config, read_rrclone_config, write_rrclone_config, ConfigStruct,
+                DriveStruct, FilterSetStruct, ServeOptionsStruct, SettingsStruct, SyncJobStruct,
+            },
+            crypt::crypt::{create_crypt, crypt_form, generate_missing},
+            daemon::daemon::{SchedulerLockStruct, TICK},
+            filter::filter::{filter_args, FilterEditorStruct, FilterInput, FilterTarget},
+            form::form::FormStruct,
+            fstab::fstab::{autofs_entry, fstab_entry, write_entries},
+            gdrive::gdrive::{
+                drive_fields, drive_params, normalize_drive_form, shared_drive_field,
+                validate_service_account, SharedFetchStruct,
+            },
+            history::history::{
+                export_report, read_history, read_log, HistoryEntryStruct, ReportFormat,
+            },
+            jobs::jobs::{start_queued, JobState, JobStruct},
+            mount::mount::{
+                active_mount_points, is_point_active, start_mounting, stop_mounting, MountState,
+                MountStatusStruct, RcStruct,
+            },
+            picker::picker::{list_dir, start_dir, EntryStruct},
+            providers::providers::{
+                provider_form, provider_params, read_providers, validate_provider_form,
+                ProviderStruct, NAME_KEY,
+            },
+            quota::quota::QuotaCacheStruct,
+            remote::remote::{
+                changed_options, delete_remote, duplicate_remote, edit_form, name_form,
+                rename_remote, update_remote, RemoteAction, NEW_NAME_KEY,
+            },
+            schedule::schedule::{due_jobs, now, reschedule},
+            serve::serve::{
+                default_port, options_from_form, serve_form, start_serving, stop_serving, PROTOCOLS,
+            },
+            sync::sync::{
+                deletes, start_preview, sync_args, sync_form, sync_from_form, sync_label,
+                PreviewStruct,
+            },
+            usage::usage::{UsageEntryStruct, UsageScanStruct, UsageSort},
+            utils::utils::{format_size, log_error, log_info, log_warning, osc52, parse_size},
         },
         ui::{
-            drive_ui::drive_ui::drive_ui, error_ui::error_ui::error_ui, main_ui::main_ui::main_ui,
+            auth_ui::auth_ui::auth_ui,
+            browser_ui::browser_ui::browser_ui,
+            cache_ui::cache_ui::cache_ui,
+            commander_ui::commander_ui::commander_ui,
+            drive_ui::drive_ui::drive_ui,
+            error_ui::error_ui::error_ui,
+            filter_ui::filter_ui::filter_ui,
+            form_ui::form_ui::form_screen_ui,
+            fstab_ui::fstab_ui::fstab_ui,
+            headless_ui::headless_ui::headless_ui,
+            history_ui::history_ui::{history_ui, log_ui},
+            jobs_ui::jobs_ui::jobs_ui,
+            main_ui::main_ui::main_ui,
+            picker_ui::picker_ui::picker_ui,
+            serve_ui::serve_ui::serve_ui,
+            sync_ui::sync_ui::sync_ui,
+            usage_ui::usage_ui::usage_ui,
+            wizard_ui::wizard_ui::wizard_ui,
         },
     };
 
@@ -40,7 +110,7 @@ pub mod app_mod {
     }
 
     impl<DriveStruct: std::clone::Clone> StatefulList<DriveStruct> {
-        pub fn with_items(items: &Vec<DriveStruct>) -> StatefulList<DriveStruct> {
+        pub fn with_items(items: &[DriveStruct]) -> StatefulList<DriveStruct> {
             StatefulList {
                 state: ListState::default(),
                 items: items.to_vec(),
@@ -48,6 +118,9 @@ pub mod app_mod {
         }
 
         pub fn next(&mut self) {
+            if self.items.is_empty() {
+                return;
+            }
             let i = match self.state.selected() {
                 Some(i) => {
                     if i >= self.items.len() - 1 {
@@ -62,6 +135,9 @@ pub mod app_mod {
         }
 
         pub fn previous(&mut self) {
+            if self.items.is_empty() {
+                return;
+            }
             let i = match self.state.selected() {
                 Some(i) => {
                     if i == 0 {
@@ -81,13 +157,106 @@ pub mod app_mod {
         pub error_temp_idx: u8,
         pub terminal: Terminal<CrosstermBackend<Stdout>>,
         pub rclone_conf: ConfigStruct,
+        pub settings: SettingsStruct,
+        pub quotas: QuotaCacheStruct,
         pub drives: StatefulList<DriveStruct>,
         pub main_message: &'a str,
         pub drive_message: &'a str,
         pub drives_mounted: Vec<DriveStruct>,
         pub processes_mounted: Vec<Child>,
+        pub rc_mounted: Vec<RcStruct>,
+        pub points_mounted: Vec<String>,
+        pub mounts_failed: Vec<String>,
         pub insert_mode: bool,
         pub new_name: String,
+        pub fstab_entries: Vec<String>,
+        pub fstab_message: &'a str,
+        pub serve_form: FormStruct,
+        pub serve_message: &'a str,
+        pub serves_running: Vec<ServeOptionsStruct>,
+        pub processes_served: Vec<Child>,
+        pub bwlimit_form: FormStruct,
+        pub bwlimit_message: &'a str,
+        pub caches: StatefulList<CacheStruct>,
+        pub cache_message: &'a str,
+        pub cache_threshold: String,
+        pub cache_insert_mode: bool,
+        pub cache_disk: Option<(u64, u64)>,
+        pub cache_scan: Option<Receiver<CacheScan>>,
+        pub cache_confirm: bool,
+        pub providers: StatefulList<ProviderStruct>,
+        pub wizard_form: FormStruct,
+        pub wizard_stage: u8,
+        pub wizard_message: &'a str,
+        pub auth: Option<AuthStruct>,
+        pub headless: HeadlessStruct,
+        pub headless_message: &'a str,
+        /**
+         * Stored choice or an SSH session, the detection itself isnt saved
+         */
+        pub headless_auth: bool,
+        pub edit_form: FormStruct,
+        pub edit_message: &'a str,
+        pub shared_fetch: Option<SharedFetchStruct>,
+        pub drive_confirm: bool,
+        pub remote_action: RemoteAction,
+        pub name_form: FormStruct,
+        pub name_message: &'a str,
+        pub crypt_form: FormStruct,
+        pub crypt_message: &'a str,
+        pub composer_form: FormStruct,
+        pub composer_message: &'a str,
+        pub gdrive_form: FormStruct,
+        pub gdrive_message: &'a str,
+        pub picker_dir: PathBuf,
+        pub picker_entries: StatefulList<EntryStruct>,
+        pub picker_return: u8,
+        pub picker_message: &'a str,
+        pub browse_remote: String,
+        pub browse_path: String,
+        pub browse_files: StatefulList<FileStruct>,
+        pub browse_listing: Option<ListingStruct>,
+        pub browse_sort: SortKey,
+        pub browse_reverse: bool,
+        pub browse_filtered: bool,
+        pub browse_message: &'a str,
+        pub commander_panes: Vec<PaneStruct>,
+        pub commander_active: usize,
+        pub commander_op: Option<CommanderOp>,
+        pub commander_input: String,
+        pub commander_message: &'a str,
+        pub jobs: Vec<JobStruct>,
+        pub jobs_state: ListState,
+        pub jobs_return: u8,
+        pub jobs_message: &'a str,
+        pub syncs_state: ListState,
+        pub sync_form: FormStruct,
+        pub sync_editing: Option<String>,
+        pub sync_preview: Option<PreviewStruct>,
+        pub sync_confirm: bool,
+        pub sync_message: &'a str,
+        pub sync_form_message: &'a str,
+        pub schedule_checked: i64,
+        pub scheduler: Option<SchedulerLockStruct>,
+        pub schedules_deferred: bool,
+        pub history: Vec<HistoryEntryStruct>,
+        pub history_state: ListState,
+        pub history_filter: String,
+        pub history_filtering: bool,
+        pub history_log: Vec<String>,
+        pub history_scroll: u16,
+        pub history_return: u8,
+        pub history_message: String,
+        pub filter_editor: Option<FilterEditorStruct>,
+        pub filter_return: u8,
+        pub filter_message: String,
+        pub usage: Option<UsageScanStruct>,
+        pub usage_dir: String,
+        pub usage_state: ListState,
+        pub usage_sort: UsageSort,
+        pub usage_confirm: bool,
+        pub usage_return: u8,
+        pub usage_message: &'a str,
     }
 
     impl App<'_> {
@@ -129,14 +298,105 @@ pub mod app_mod {
                 error_temp_idx: 0,
                 terminal,
                 rclone_conf: rclone_conf.clone(),
+                settings: read_rrclone_config(),
+                quotas: QuotaCacheStruct::load(),
                 drives: StatefulList::with_items(&rclone_conf.drives),
                 main_message: "Use Arrow keys to navigate drives and press Enter",
                 drive_message: "Managing drives",
                 drives_mounted: vec![],
                 processes_mounted: vec![],
+                rc_mounted: vec![],
+                points_mounted: vec![],
+                mounts_failed: vec![],
                 insert_mode: false,
                 new_name: String::new(),
+                fstab_entries: vec![],
+                fstab_message: "Permanent mount entries",
+                serve_form: FormStruct::default(),
+                serve_message: "Serve a remote without FUSE",
+                serves_running: vec![],
+                processes_served: vec![],
+                bwlimit_form: FormStruct::default(),
+                bwlimit_message: "Bandwidth limits",
+                caches: StatefulList::with_items(&[]),
+                cache_message: "VFS caches",
+                cache_threshold: String::new(),
+                cache_insert_mode: false,
+                cache_disk: None,
+                cache_scan: None,
+                cache_confirm: false,
+                providers: StatefulList::with_items(&[]),
+                wizard_form: FormStruct::default(),
+                wizard_stage: 0,
+                wizard_message: "Pick a backend for the new remote",
+                auth: None,
+                headless: HeadlessStruct::default(),
+                headless_message: "Authorize on another machine",
+                headless_auth: false,
+                edit_form: FormStruct::default(),
+                edit_message: "Editing remote",
+                shared_fetch: None,
+                drive_confirm: false,
+                remote_action: RemoteAction::Rename,
+                name_form: FormStruct::default(),
+                name_message: "Pick a new name",
+                crypt_form: FormStruct::default(),
+                crypt_message: "Encrypted remote",
+                composer_form: FormStruct::default(),
+                composer_message: "Compose remotes",
+                gdrive_form: FormStruct::default(),
+                gdrive_message: "Google Drive options",
+                picker_dir: PathBuf::new(),
+                picker_entries: StatefulList::with_items(&[]),
+                picker_return: 1,
+                picker_message: "Pick a file",
+                browse_remote: String::new(),
+                browse_path: String::new(),
+                browse_files: StatefulList::with_items(&[]),
+                browse_listing: None,
+                browse_sort: SortKey::Name,
+                browse_reverse: false,
+                browse_filtered: false,
+                browse_message: "Browsing",
+                commander_panes: vec![],
+                commander_active: 0,
+                commander_op: None,
+                commander_input: String::new(),
+                commander_message: "Local and remote side by side",
+                jobs: vec![],
+                jobs_state: ListState::default(),
+                jobs_return: 0,
+                jobs_message: "Transfers run in the background",
+                syncs_state: ListState::default(),
+                sync_form: FormStruct::default(),
+                sync_editing: None,
+                sync_preview: None,
+                sync_confirm: false,
+                sync_message: "Saved copy, sync and bisync jobs",
+                sync_form_message: "Sync job",
+                schedule_checked: 0,
+                scheduler: None,
+                schedules_deferred: false,
+                history: vec![],
+                history_state: ListState::default(),
+                history_filter: String::new(),
+                history_filtering: false,
+                history_log: vec![],
+                history_scroll: 0,
+                history_return: 0,
+                history_message: String::from("Finished jobs, newest first"),
+                filter_editor: None,
+                filter_return: 0,
+                filter_message: String::new(),
+                usage: None,
+                usage_dir: String::new(),
+                usage_state: ListState::default(),
+                usage_sort: UsageSort::Size,
+                usage_confirm: false,
+                usage_return: 0,
+                usage_message: "Biggest first, like rclone ncdu",
             };
+            app.headless_auth = app.settings.headless_auth || is_headless();
             app.drives.state.select(Some(0));
             app
         }
@@ -147,8 +407,49 @@ pub mod app_mod {
                     0 => self.go_main(),
                     1 => self.go_drives(),
                     2 => {}
+                    3 => self.go_fstab(),
+                    4 => self.go_serve(),
+                    5 => self.go_bwlimit(),
+                    6 => self.go_cache(),
+                    7 => self.go_wizard(),
+                    8 => self.go_auth(),
+                    9 => self.go_headless(),
+                    10 => self.go_edit(),
+                    11 => self.go_name(),
+                    12 => self.go_crypt(),
+                    13 => self.go_composer(),
+                    14 => self.go_gdrive(),
+                    15 => self.go_picker(),
+                    16 => self.go_browser(),
+                    17 => self.go_commander(),
+                    18 => self.go_jobs(),
+                    19 => self.go_sync(),
+                    20 => self.go_sync_form(),
+                    21 => self.go_history(),
+                    22 => self.go_log(),
+                    23 => self.go_filter(),
+                    24 => self.go_usage(),
                     _ => panic!("Screen not found"),
                 };
+                self.supervise();
+                self.poll_auth();
+                self.poll_listing();
+                self.poll_jobs();
+                self.poll_quotas();
+                self.poll_caches();
+                self.poll_shared_drives();
+                self.run_schedules();
+                if let Some(preview) = self.sync_preview.as_mut() {
+                    preview.poll();
+                }
+                if let Some(preview) = self.filter_editor.as_mut().and_then(|e| e.preview.as_mut())
+                {
+                    preview.poll();
+                }
+                if let Some(usage) = self.usage.as_mut() {
+                    usage.poll();
+                }
+                self.poll_usage_deletes();
                 if poll(Duration::from_millis(500))? {
                     match event::read().unwrap() {
                         Event::Resize(width, height) => {
@@ -162,137 +463,698 @@ pub mod app_mod {
                                 self.error_temp_idx = 0;
                             }
                         }
-                        Event::Key(key) => match &self.ui_idx {
-                            0 => {
-                                if key.kind == KeyEventKind::Press {
-                                    match key.modifiers {
-                                        KeyModifiers::SHIFT => {
-                                            if key.code == KeyCode::Right {
-                                                println!("SHIFT + Right");
-                                            }
+                        Event::Paste(text) => self.on_paste(text),
+                        Event::Key(key) if key.kind == KeyEventKind::Press => {
+                            let quit = match self.ui_idx {
+                                0 => self.main_keys(key),
+                                1 => self.drive_keys(key),
+                                3 => self.fstab_keys(key),
+                                4 => self.serve_keys(key),
+                                5 => self.bwlimit_keys(key),
+                                6 => self.cache_keys(key),
+                                7 => self.wizard_keys(key),
+                                8 => self.auth_keys(key),
+                                9 => self.headless_keys(key),
+                                10 => self.edit_keys(key),
+                                11 => self.name_keys(key),
+                                12 => self.crypt_keys(key),
+                                13 => self.composer_keys(key),
+                                14 => self.gdrive_keys(key),
+                                15 => self.picker_keys(key),
+                                16 => self.browser_keys(key),
+                                17 => self.commander_keys(key),
+                                18 => self.jobs_keys(key),
+                                19 => self.sync_keys(key),
+                                20 => self.sync_form_keys(key),
+                                21 => self.history_keys(key),
+                                22 => self.log_keys(key),
+                                23 => self.filter_keys(key),
+                                24 => self.usage_keys(key),
+                                _ => false,
+                            };
+                            if quit {
+                                return Ok(());
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+            }
+        }
+
+        /**
+         * Unmounts everything before leaving, returns true to quit
+         */
+        fn quit(&mut self) -> bool {
+            if let Some(auth) = self.auth.as_mut() {
+                auth.cancel();
+            }
+            if let Some(listing) = self.browse_listing.as_mut() {
+                listing.cancel();
+            }
+            for pane in self.commander_panes.iter_mut() {
+                if let Some(listing) = pane.listing.as_mut() {
+                    listing.cancel();
+                }
+            }
+            for job in self.jobs.iter_mut() {
+                job.cancel();
+            }
+            if let Some(preview) = self.sync_preview.as_mut() {
+                preview.cancel();
+            }
+            if let Some(preview) = self.filter_editor.as_mut().and_then(|e| e.preview.as_mut()) {
+                preview.cancel();
+            }
+            if let Some(usage) = self.usage.as_mut() {
+                usage.cancel();
+            }
+            self.scheduler = None;
+            let processes: Vec<Child> = self.processes_mounted.drain(..).collect();
+            for (index, process) in processes.into_iter().enumerate() {
+                stop_mounting(
+                    &self.drives_mounted[index],
+                    &self.points_mounted[index],
+                    process,
+                );
+            }
+            self.drives_mounted.clear();
+            self.rc_mounted.clear();
+            self.points_mounted.clear();
+            let processes: Vec<Child> = self.processes_served.drain(..).collect();
+            for (index, process) in processes.into_iter().enumerate() {
+                stop_serving(&self.serves_running[index], process);
+            }
+            self.serves_running.clear();
+            true
+        }
+
+        /**
+         * Forgets mounts and serves whose rclone process died on its own
+         */
+        fn supervise(&mut self) {
+            let mut index = 0;
+            while index < self.processes_mounted.len() {
+                if let Ok(Some(status)) = self.processes_mounted[index].try_wait() {
+                    log_error(format!(
+                        "Mount of {} exited ({})",
+                        self.drives_mounted[index].name, status
+                    ));
+                    let _ = self.processes_mounted.remove(index).wait();
+                    let drive = self.drives_mounted.remove(index);
+                    self.rc_mounted.remove(index);
+                    self.points_mounted.remove(index);
+                    self.mounts_failed.push(drive.name);
+                    self.main_message = "A mount stopped unexpectedly, check logs";
+                } else {
+                    index += 1;
+                }
+            }
+            let mut index = 0;
+            while index < self.processes_served.len() {
+                if let Ok(Some(status)) = self.processes_served[index].try_wait() {
+                    log_error(format!(
+                        "Serving {} exited ({})",
+                        self.serves_running[index].name, status
+                    ));
+                    let _ = self.processes_served.remove(index).wait();
+                    self.serves_running.remove(index);
+                    self.serve_message = "A serve stopped unexpectedly, check logs";
+                } else {
+                    index += 1;
+                }
+            }
+        }
+
+        /**
+         * Writes the settings, run times the scheduler saved meanwhile are kept
+         */
+        fn save_settings(&mut self) {
+            self.settings.merge_run_times(&read_rrclone_config());
+            if let Err(e) = write_rrclone_config(&self.settings) {
+                log_error(e.to_string());
+            }
+        }
+
+        fn refresh_drives(&mut self) {
+            let rclone_conf = read_rclone_config();
+            self.rclone_conf = rclone_conf.clone();
+            self.drives = StatefulList::with_items(&rclone_conf.drives);
+            self.drives.state.select(Some(0));
+        }
+
+        fn select_drive(&mut self, name: &str) {
+            if let Some(i) = self.drives.items.iter().position(|d| d.name == name) {
+                self.drives.state.select(Some(i));
+            }
+        }
+
+        fn show_auth(&mut self, auth: Result<AuthStruct, String>) {
+            match auth {
+                Ok(auth) => {
+                    if let Some(previous) = self.auth.as_mut() {
+                        previous.cancel();
+                    }
+                    self.auth = Some(auth);
+                    self.ui_idx = 8;
+                }
+                Err(e) => {
+                    log_error(e);
+                    self.drive_message = "Couldnt start rclone, check logs";
+                    self.ui_idx = 1;
+                }
+            }
+        }
+
+        fn show_headless(&mut self, name: &str, backend: &str, params: Vec<String>) {
+            self.headless = HeadlessStruct {
+                name: name.trim().to_owned(),
+                backend: backend.to_owned(),
+                params,
+                token: String::new(),
+            };
+            self.headless_message = "Authorize on another machine";
+            self.ui_idx = 9;
+        }
+
+        /**
+         * Bracketed paste arrives in one piece, only text inputs take it
+         */
+        fn on_paste(&mut self, text: String) {
+            match self.ui_idx {
+                1 if self.insert_mode => self.new_name.push_str(text.trim()),
+                9 => {
+                    self.headless.token.push_str(&text);
+                    self.headless_message = match validate_token(&self.headless.token) {
+                        Ok(_) => "Token looks good, press Enter",
+                        Err(_) => "Pasted, but this is not a complete token yet",
+                    };
+                }
+                _ => {}
+            }
+        }
+
+        /**
+         * Follows a running login, reloads drives once the remote exists
+         */
+        fn poll_auth(&mut self) {
+            let auth = match self.auth.as_mut() {
+                Some(auth) => auth,
+                None => return,
+            };
+            if auth.poll() && auth.status == AuthStatus::Success {
+                let name = auth.name.clone();
+                // other screens work on the selected remote, it has to stay the same one
+                let current = self
+                    .drives
+                    .state
+                    .selected()
+                    .and_then(|i| self.drives.items.get(i))
+                    .map(|d| d.name.clone());
+                self.refresh_drives();
+                match (self.ui_idx, current) {
+                    (1 | 8, _) | (_, None) => self.select_drive(&name),
+                    (_, Some(current)) => self.select_drive(&current),
+                }
+                self.drive_message = "New remote added";
+                // Drive remotes get to pick a shared drive once rclone lists them
+                if self
+                    .drives
+                    .items
+                    .iter()
+                    .any(|d| d.name == name && d.drive_type == "drive")
+                {
+                    self.shared_fetch = Some(SharedFetchStruct::start(&name, true));
+                }
+            }
+        }
+
+        /**
+         * Fills the shared drive picker of the edit form, after a login it opens the form
+         * when the account has shared drives
+         */
+        fn poll_shared_drives(&mut self) {
+            let (shared, name, added) = match self.shared_fetch.as_ref() {
+                Some(fetch) => match fetch.poll() {
+                    Some(shared) => (shared, fetch.name.clone(), fetch.added),
+                    None => return,
+                },
+                None => return,
+            };
+            self.shared_fetch = None;
+            let drive = match self.drives.items.iter().find(|d| d.name == name) {
+                Some(drive) => drive.clone(),
+                None => return,
+            };
+            let editing = self.ui_idx == 10
+                && self
+                    .drives
+                    .state
+                    .selected()
+                    .and_then(|i| self.drives.items.get(i))
+                    .is_some_and(|d| d.name == name);
+            match shared {
+                Err(e) => {
+                    log_error(e);
+                    if editing {
+                        self.edit_message = "Couldnt list shared drives, check logs";
+                    }
+                }
+                Ok(shared) if editing => {
+                    shared_drive_field(&mut self.edit_form, &drive, &shared);
+                    self.edit_message = "Editing ...";
+                }
+                Ok(shared) if added && !shared.is_empty() && matches!(self.ui_idx, 1 | 8) => {
+                    self.select_drive(&name);
+                    self.edit_form = edit_form(&drive);
+                    drive_fields(&mut self.edit_form, Some(&drive), &shared);
+                    if let Some(i) = self
+                        .edit_form
+                        .fields
+                        .iter()
+                        .position(|f| f.key == "team_drive")
+                    {
+                        self.edit_form.selected = i;
+                    }
+                    self.edit_message = "Pick a shared drive with Left/Right, Enter saves";
+                    self.ui_idx = 10;
+                }
+                Ok(_) => {}
+            }
+        }
+
+        fn main_keys(&mut self, key: KeyEvent) -> bool {
+            if self.drives.items.is_empty()
+                && !matches!(key.code, KeyCode::Char('q' | 'd' | 'p' | 'j' | 'y' | 'h'))
+            {
+                self.main_message = "No remotes yet, add one from the drives screen 'd'";
+                return false;
+            }
+            match key.code {
+                KeyCode::Char('q') => return self.quit(),
+                KeyCode::Char('d') => self.ui_idx = 1,
+                KeyCode::Char('f') => {
+                    let i = self.drives.state.selected().unwrap();
+                    let drive = &self.drives.items[i];
+                    let options = self.settings.mount_options(&drive.name);
+                    match (fstab_entry(drive, &options), autofs_entry(drive, &options)) {
+                        (Ok(fstab), Ok(autofs)) => {
+                            self.fstab_entries = vec![fstab, autofs];
+                            self.fstab_message = "Permanent mount entries";
+                            self.ui_idx = 3;
+                        }
+                        (Err(e), _) | (_, Err(e)) => {
+                            log_warning(format!("No mount entries for {}: {}", drive.name, e));
+                            self.main_message = "A mount option holds a comma, check logs";
+                        }
+                    }
+                }
+                KeyCode::Char('r') => {
+                    self.drive_message = "Refreshing list of drives";
+                    self.refresh_drives();
+                }
+                KeyCode::Char('o') => {
+                    let i = self.drives.state.selected().unwrap();
+                    let drive = self.drives.items[i].clone();
+                    let mut options = self.settings.stored_mount_options(&drive.name);
+                    options.read_only = !options.read_only;
+                    self.settings.set_mount_options(&options);
+                    self.save_settings();
+                    log_info(format!(
+                        "{} is now mounted {}",
+                        drive.name,
+                        if options.read_only {
+                            "read-only"
+                        } else {
+                            "read-write"
+                        }
+                    ));
+                    self.main_message = if self.drives_mounted.iter().any(|d| d.name == drive.name)
+                    {
+                        "Read-only changed, re-mount to apply"
+                    } else {
+                        "Read-only changed"
+                    };
+                }
+                KeyCode::Char('p') => {
+                    self.settings.safe_mode = !self.settings.safe_mode;
+                    self.save_settings();
+                    self.main_message = if self.settings.safe_mode {
+                        "Safe mode on, new mounts are read-only"
+                    } else {
+                        "Safe mode off"
+                    };
+                }
+                KeyCode::Char('e') => {
+                    let i = self.drives.state.selected().unwrap();
+                    let remote = self.drives.items[i].name.clone();
+                    self.browse(&remote, "");
+                    self.ui_idx = 16;
+                }
+                KeyCode::Char('j') => {
+                    self.jobs_return = 0;
+                    self.ui_idx = 18;
+                }
+                KeyCode::Char('h') => self.open_history(0),
+                KeyCode::Char('u') => {
+                    let i = self.drives.state.selected().unwrap();
+                    let remote = self.drives.items[i].name.clone();
+                    self.open_usage(&remote, "", 0);
+                }
+                KeyCode::Char('i') => {
+                    let i = self.drives.state.selected().unwrap();
+                    let name = self.drives.items[i].name.clone();
+                    let options = self.settings.stored_mount_options(&name);
+                    self.open_filter(
+                        FilterTarget::Mount(name.clone()),
+                        &options.filter,
+                        &format!("{}:", name),
+                        0,
+                    );
+                }
+                KeyCode::Char('y') => {
+                    if self.syncs_state.selected().is_none() && !self.settings.syncs.is_empty() {
+                        self.syncs_state.select(Some(0));
+                    }
+                    self.ui_idx = 19;
+                }
+                KeyCode::Char('t') => {
+                    let i = self.drives.state.selected().unwrap();
+                    let remote = self.drives.items[i].name.clone();
+                    if self.commander_panes.is_empty() {
+                        let home = env::var("HOME").unwrap_or(String::from("/"));
+                        self.commander_panes = vec![
+                            PaneStruct::new(None, &home),
+                            PaneStruct::new(Some(remote), ""),
+                        ];
+                    } else if self.commander_panes[1].remote.as_ref() != Some(&remote) {
+                        self.commander_panes[1].switch(Some(remote), "");
+                    }
+                    self.commander_op = None;
+                    self.ui_idx = 17;
+                }
+                KeyCode::Char('c') => {
+                    self.cache_threshold = self.settings.cache_warn_size.clone();
+                    self.cache_insert_mode = false;
+                    self.cache_confirm = false;
+                    self.refresh_caches();
+                    self.ui_idx = 6;
+                }
+                KeyCode::Char('b') => {
+                    let i = self.drives.state.selected().unwrap();
+                    let options = self
+                        .settings
+                        .stored_mount_options(&self.drives.items[i].name);
+                    self.bwlimit_form = bwlimit_form(&options.bwlimit, &self.settings.bwlimit);
+                    self.bwlimit_message = "Bandwidth limits, empty means unlimited";
+                    self.ui_idx = 5;
+                }
+                KeyCode::Char('s') => {
+                    let i = self.drives.state.selected().unwrap();
+                    let options = self.settings.serve_options(&self.drives.items[i].name);
+                    self.serve_form = serve_form(&options);
+                    self.serve_message = "Serve a remote without FUSE";
+                    self.ui_idx = 4;
+                }
+                KeyCode::Down => self.drives.next(),
+                KeyCode::Up => self.drives.previous(),
+                KeyCode::Enter => {
+                    let i = self.drives.state.selected().unwrap();
+                    let mounted = self.drives.items[i].clone();
+                    if self.drives_mounted.iter().any(|d| d.name == mounted.name) {
+                        self.main_message = "No need to re-mount same drive ^_^";
+                    } else {
+                        self.mounts_failed.retain(|name| name != &mounted.name);
+                        let full = self
+                            .quotas
+                            .quota(&mounted.name)
+                            .is_some_and(|q| q.is_full(self.settings.quota_warn));
+                        if start_mounting(&mounted, self) {
+                            self.main_message = match full {
+                                true => "Mounting ..., this remote is almost full",
+                                false => "Mounting ...",
+                            };
+                        } else {
+                            self.mounts_failed.push(mounted.name);
+                            self.main_message = "Couldnt start rclone, check logs";
+                        }
+                    }
+                }
+                KeyCode::Delete => {
+                    let i = self.drives.state.selected().unwrap();
+                    let mounted = self.drives.items[i].clone();
+                    if let Some(i) = self
+                        .drives_mounted
+                        .iter()
+                        .position(|x| x.name == mounted.name)
+                    {
+                        self.main_message = "Unmounting ...";
+                        let point = self.points_mounted.remove(i);
+                        stop_mounting(&mounted, &point, self.processes_mounted.remove(i));
+                        self.drives_mounted.remove(i);
+                        self.rc_mounted.remove(i);
+                    } else if self.mounts_failed.contains(&mounted.name) {
+                        self.mounts_failed.retain(|name| name != &mounted.name);
+                        self.main_message = "Failed mount cleared";
+                    } else {
+                        self.main_message = "There is no drive to unmount!"
+                    }
+                }
+                _ => {}
+            }
+            false
+        }
+
+        fn drive_keys(&mut self, key: KeyEvent) -> bool {
+            let confirm = self.drive_confirm;
+            self.drive_confirm = false;
+            match key.code {
+                KeyCode::Esc => self.insert_mode = false,
+                KeyCode::Down => self.drives.next(),
+                KeyCode::Up => self.drives.previous(),
+                KeyCode::Enter => {
+                    if self.insert_mode {
+                        self.drive_message = "Exit insert mode first 'Esc'";
+                    } else if let Err(e) = self.rclone_conf.validate_name(self.new_name.trim()) {
+                        log_warning(format!("Invalid remote name '{}': {}", self.new_name, e));
+                        self.drive_message = "Fix the remote name first";
+                    } else {
+                        self.insert_mode = false;
+                        self.gdrive_form = FormStruct::default();
+                        drive_fields(&mut self.gdrive_form, None, &[]);
+                        self.gdrive_message = "Shared drives can be picked with 'e' once added";
+                        self.ui_idx = 14;
+                    }
+                }
+                KeyCode::Backspace => {
+                    self.new_name.pop();
+                }
+                KeyCode::Tab if self.insert_mode => {
+                    self.new_name = self.rclone_conf.suggest_name(self.new_name.trim());
+                }
+                KeyCode::Char(c) => {
+                    if self.insert_mode {
+                        self.new_name.push(c);
+                    } else {
+                        match c {
+                            'i' => self.insert_mode = true,
+                            'h' => {
+                                self.headless_auth = !self.headless_auth;
+                                self.settings.headless_auth = self.headless_auth;
+                                self.save_settings();
+                                self.drive_message = match self.headless_auth {
+                                    true => "Headless login, authorize on another machine",
+                                    false => "Local login, a browser opens here",
+                                };
+                            }
+                            'l' => match self.auth {
+                                Some(_) => self.ui_idx = 8,
+                                None => self.drive_message = "No login in progress",
+                            },
+                            'm' => self.ui_idx = 0,
+                            'q' => return self.quit(),
+                            'a' => {
+                                if let Some(drive) = self
+                                    .drives
+                                    .state
+                                    .selected()
+                                    .and_then(|i| self.drives.items.get(i))
+                                {
+                                    self.quotas.request(&drive.name, true);
+                                    self.drive_message = "Asking rclone about the quota ...";
+                                }
+                            }
+                            '+' | '-' => {
+                                self.settings.quota_warn = match c {
+                                    '+' => (self.settings.quota_warn + 5).min(100),
+                                    _ => self.settings.quota_warn.saturating_sub(5).max(50),
+                                };
+                                self.save_settings();
+                                log_info(format!(
+                                    "Quota warning at {}% full",
+                                    self.settings.quota_warn
+                                ));
+                                self.drive_message = "Quota warning threshold changed";
+                            }
+                            'w' => {
+                                if self.providers.items.is_empty() {
+                                    match read_providers() {
+                                        Ok(providers) => {
+                                            self.providers = StatefulList::with_items(&providers);
+                                            self.providers.state.select(Some(0));
                                         }
-                                        _ => {}
+                                        Err(e) => log_error(e),
                                     }
-                                    match key.code {
-                                        KeyCode::Char('q') => {
-                                            for index in 0..self.processes_mounted.len() {
-                                                stop_mounting(
-                                                    &self.drives_mounted[index],
-                                                    &mut self.processes_mounted[index],
-                                                );
-                                            }
-                                            return Ok(());
-                                        }
-                                        KeyCode::Char('d') => self.ui_idx = 1,
-                                        KeyCode::Char('r') => {
-                                            self.drive_message = "Refreshing list of drives";
-                                            let rclone_conf = read_rclone_config();
-                                            self.rclone_conf = rclone_conf.clone();
-                                            self.drives =
-                                                StatefulList::with_items(&rclone_conf.drives);
-                                            self.drives.state.select(Some(0));
-                                        }
-                                        KeyCode::Down => self.drives.next(),
-                                        KeyCode::Up => self.drives.previous(),
-                                        KeyCode::Enter => {
-                                            let i = self.drives.state.selected().unwrap();
-                                            let mounted = self.drives.items[i].clone();
-                                            if self.drives_mounted.contains(&mounted) {
-                                                self.main_message =
-                                                    "No need to re-mount same drive ^_^";
-                                            } else {
-                                                self.main_message = "Mounting ...";
-                                                self.drives_mounted.push(mounted.clone());
-                                                start_mounting(&mounted, self);
-                                            }
-                                        }
-                                        KeyCode::Delete => {
-                                            let i = self.drives.state.selected().unwrap();
-                                            let mounted = self.drives.items[i].clone();
-                                            if self.drives_mounted.contains(&mounted) {
-                                                self.main_message = "Unmounting ...";
-                                                let i = self
-                                                    .drives_mounted
-                                                    .iter()
-                                                    .position(|x| x == &mounted)
-                                                    .unwrap();
-                                                stop_mounting(
-                                                    &mounted,
-                                                    &mut self.processes_mounted[i],
-                                                );
-                                                self.drives_mounted.remove(i);
-                                                self.processes_mounted.remove(i);
-                                            } else {
-                                                self.main_message = "There is no drive to unmount!"
-                                            }
+                                }
+                                self.wizard_stage = 0;
+                                self.wizard_message = "Pick a backend for the new remote";
+                                self.ui_idx = 7;
+                            }
+                            'r' => {
+                                self.drive_message = "Refreshing list of drives";
+                                self.refresh_drives();
+                            }
+                            'e' => {
+                                if let Some(i) = self.drives.state.selected() {
+                                    if i < self.drives.items.len() {
+                                        let drive = &self.drives.items[i];
+                                        self.edit_form = edit_form(drive);
+                                        self.edit_message = "Editing ...";
+                                        if drive.drive_type == "drive" {
+                                            drive_fields(&mut self.edit_form, Some(drive), &[]);
+                                            self.shared_fetch =
+                                                Some(SharedFetchStruct::start(&drive.name, false));
+                                            self.edit_message = "Listing shared drives ...";
                                         }
-                                        _ => {}
+                                        self.ui_idx = 10;
                                     }
                                 }
                             }
-                            1 => {
-                                if key.kind == KeyEventKind::Press {
-                                    match key.code {
-                                        KeyCode::Esc => self.insert_mode = false,
-                                        KeyCode::Down => self.drives.next(),
-                                        KeyCode::Up => self.drives.previous(),
-                                        KeyCode::Enter => {
-                                            if self.insert_mode {
-                                                self.drive_message = "Exit insert mode first 'Esc'";
-                                            } else {
-                                                self.drive_message =
-                                                    "After login, press 'r' to refresh";
-                                                self.insert_mode = false;
-                                                login_google_drive(self.new_name.clone());
-                                            }
-                                        }
-                                        KeyCode::Backspace => {
-                                            self.new_name.pop();
-                                        }
-                                        KeyCode::Char(c) => {
-                                            if self.insert_mode {
-                                                self.new_name.push(c);
-                                            } else {
-                                                match c {
-                                                    'i' => self.insert_mode = true,
-                                                    'm' => self.ui_idx = 0,
-                                                    'q' => {
-                                                        for index in 0..self.processes_mounted.len()
-                                                        {
-                                                            stop_mounting(
-                                                                &self.drives_mounted[index],
-                                                                &mut self.processes_mounted[index],
-                                                            );
-                                                        }
-                                                        return Ok(());
-                                                    }
-                                                    'r' => {
-                                                        self.drive_message =
-                                                            "Refreshing list of drives";
-                                                        let rclone_conf = read_rclone_config();
-                                                        self.rclone_conf = rclone_conf.clone();
-                                                        self.drives = StatefulList::with_items(
-                                                            &rclone_conf.drives,
-                                                        );
-                                                        self.drives.state.select(Some(0));
-                                                    }
-                                                    // 'e' => {
-                                                    //     self.drive_message = "Editing ...";
-                                                    // }
-                                                    _ => {}
-                                                }
-                                            }
-                                        }
-                                        _ => {}
+                            'k' => {
+                                if let Some(i) = self.drives.state.selected() {
+                                    if i < self.drives.items.len() {
+                                        self.crypt_form =
+                                            crypt_form(&self.rclone_conf, &self.drives.items[i]);
+                                        self.crypt_message = "Encrypt a folder of this remote";
+                                        self.ui_idx = 12;
                                     }
                                 }
                             }
+                            'u' => {
+                                if let Some(i) = self.drives.state.selected() {
+                                    if i < self.drives.items.len() {
+                                        self.composer_form = composer_form(
+                                            &self.rclone_conf,
+                                            &self.drives.items[i],
+                                            None,
+                                        );
+                                        self.composer_message =
+                                            "Give a path to every remote to include";
+                                        self.ui_idx = 13;
+                                    }
+                                }
+                            }
+                            'x' => self.ask_delete(),
+                            'y' if confirm => self.delete_selected(),
+                            'n' | 'c' => {
+                                let drive = match self.drives.state.selected() {
+                                    Some(i) if i < self.drives.items.len() => &self.drives.items[i],
+                                    _ => return false,
+                                };
+                                let (action, value) = match c {
+                                    'n' => (RemoteAction::Rename, drive.name.clone()),
+                                    _ => (
+                                        RemoteAction::Duplicate,
+                                        self.rclone_conf.suggest_name(&drive.name),
+                                    ),
+                                };
+                                self.remote_action = action;
+                                self.name_form = name_form(action, &value);
+                                self.name_message = match action {
+                                    RemoteAction::Rename => "Renaming ...",
+                                    RemoteAction::Duplicate => "Duplicating ...",
+                                };
+                                self.ui_idx = 11;
+                            }
                             _ => {}
-                        },
-                        _ => {}
+                        }
+                    }
+                }
+                _ => {}
+            }
+            false
+        }
+
+        /**
+         * Warns about mounts and dependent remotes, then waits for 'y'
+         */
+        fn ask_delete(&mut self) {
+            let drive = match self.drives.state.selected() {
+                Some(i) if i < self.drives.items.len() => &self.drives.items[i],
+                _ => return,
+            };
+            if self.settings.safe_mode {
+                self.drive_message = "Safe mode is on, deleting is disabled";
+                return;
+            }
+            let in_use = self.drives_mounted.iter().any(|d| d.name == drive.name)
+                || self.serves_running.iter().any(|s| s.name == drive.name);
+            let dependents = self.rclone_conf.dependents(&drive.name);
+            if !dependents.is_empty() {
+                log_warning(format!(
+                    "{} is used by {}",
+                    drive.name,
+                    dependents.join(", ")
+                ));
+            }
+            self.drive_confirm = true;
+            self.drive_message = match (in_use, dependents.is_empty()) {
+                (true, _) => "Remote is mounted or served! 'y' to delete anyway",
+                (false, false) => "Other remotes use this one, check logs. 'y' to delete",
+                (false, true) => "Delete this remote? 'y' to confirm",
+            };
+        }
+
+        fn delete_selected(&mut self) {
+            let i = self.drives.state.selected().unwrap();
+            let name = self.drives.items[i].name.clone();
+            match delete_remote(&name) {
+                Ok(_) => {
+                    log_info(format!("Deleted remote {}", name));
+                    self.settings.rename_remote(&name, None);
+                    self.save_settings();
+                    self.refresh_drives();
+                    self.drives
+                        .state
+                        .select(Some(i.min(self.drives.items.len().saturating_sub(1))));
+                    self.drive_message = "Remote deleted";
+                }
+                Err(e) => {
+                    log_error(e);
+                    self.drive_message = "Couldnt delete the remote, check logs";
+                }
+            }
+        }
+
+        fn fstab_keys(&mut self, key: KeyEvent) -> bool {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('m') => self.ui_idx = 0,
+                KeyCode::Char('w') => {
+                    let i = self.drives.state.selected().unwrap();
+                    match write_entries(&self.drives.items[i], &self.fstab_entries) {
+                        Ok(dir) => {
+                            log_info(format!("Mount entries written to {}", dir));
+                            self.fstab_message = "Entries written next to RRclone settings";
+                        }
+                        Err(e) => {
+                            log_error(e.to_string());
+                            self.fstab_message = "Couldnt write entries";
+                        }
                     }
                 }
+                _ => {}
             }
+            false
         }
 
         pub fn exit(&mut self) {
@@ -315,26 +1177,2134 @@ pub mod app_mod {
             self.terminal.show_cursor().expect("couldnt show cursor");
         }
 
+        /**
+         * State of every listed remote, mounted only once the OS sees the mount point
+         */
+        fn mount_statuses(&self) -> Vec<MountStatusStruct> {
+            let active = active_mount_points();
+            self.drives
+                .items
+                .iter()
+                .map(|drive| {
+                    match self
+                        .drives_mounted
+                        .iter()
+                        .position(|d| d.name == drive.name)
+                    {
+                        Some(i) => MountStatusStruct {
+                            state: match is_point_active(&active, &self.points_mounted[i]) {
+                                true => MountState::Mounted,
+                                false => MountState::Mounting,
+                            },
+                            point: self.points_mounted[i].clone(),
+                        },
+                        None => MountStatusStruct {
+                            state: if self.serves_running.iter().any(|s| s.name == drive.name) {
+                                MountState::Serving
+                            } else if self.mounts_failed.contains(&drive.name) {
+                                MountState::Failed
+                            } else {
+                                MountState::Unmounted
+                            },
+                            point: String::new(),
+                        },
+                    }
+                })
+                .collect()
+        }
+
+        /**
+         * Fetches the quota of the selected remote once the cached one is stale
+         */
+        fn request_quota(&mut self) {
+            if let Some(drive) = self
+                .drives
+                .state
+                .selected()
+                .and_then(|i| self.drives.items.get(i))
+            {
+                self.quotas.request(&drive.name, false);
+            }
+        }
+
+        fn poll_quotas(&mut self) {
+            for name in self.quotas.poll() {
+                if let Some(quota) = self
+                    .quotas
+                    .quota(&name)
+                    .filter(|q| q.is_full(self.settings.quota_warn))
+                {
+                    log_warning(format!(
+                        "{} is {}% full",
+                        name,
+                        quota.percent().unwrap_or(100)
+                    ));
+                }
+            }
+        }
+
         pub fn go_main(&mut self) {
+            self.request_quota();
+            let statuses = self.mount_statuses();
             self.terminal
-                .draw(|f| main_ui(f, &self.drives, &self.main_message))
+                .draw(|f| {
+                    main_ui(
+                        f,
+                        &self.drives,
+                        &statuses,
+                        self.main_message,
+                        &self.settings,
+                        &self.quotas,
+                    )
+                })
                 .expect("Couldnt navigate to main screen");
         }
 
         pub fn go_drives(&mut self) {
+            self.request_quota();
+            let name_error = match self.new_name.is_empty() {
+                true => None,
+                false => self.rclone_conf.validate_name(self.new_name.trim()).err(),
+            };
+            let statuses = self.mount_statuses();
             self.terminal
                 .draw(|f| {
                     drive_ui(
                         f,
                         &self.drives,
-                        &self.drive_message,
+                        &statuses,
+                        self.drive_message,
+                        &self.settings,
                         self.new_name.clone(),
-                        self.insert_mode.clone(),
+                        name_error.as_deref(),
+                        self.insert_mode,
+                        &self.quotas,
                     )
                 })
                 .expect("Couldnt navigate to drive screen");
         }
 
+        pub fn go_fstab(&mut self) {
+            self.terminal
+                .draw(|f| fstab_ui(f, &self.fstab_entries, self.fstab_message))
+                .expect("Couldnt navigate to fstab screen");
+        }
+
+        fn serve_keys(&mut self, key: KeyEvent) -> bool {
+            let i = self.drives.state.selected().unwrap();
+            let name = self.drives.items[i].name.clone();
+            match key.code {
+                KeyCode::Esc => self.ui_idx = 0,
+                KeyCode::Enter => {
+                    let mut options = options_from_form(&name, &self.serve_form);
+                    options.read_only |= self.settings.mount_options(&name).read_only;
+                    if options.port.parse::<u16>().is_err() {
+                        self.serve_message = "Port must be a number";
+                    } else if self.serves_running.iter().any(|s| {
+                        (s.name == name && s.protocol == options.protocol)
+                            || (s.addr == options.addr && s.port == options.port)
+                    }) {
+                        self.serve_message = "Already serving there ^_^";
+                    } else if start_serving(&options, self) {
+                        self.settings.set_serve_options(&options);
+                        self.save_settings();
+                        self.serve_message = "Serving, listen URL on the right";
+                    } else {
+                        self.serve_message = "Couldnt start rclone serve, check logs";
+                    }
+                }
+                KeyCode::Delete => {
+                    let protocol = self.serve_form.value("protocol");
+                    match self
+                        .serves_running
+                        .iter()
+                        .position(|s| s.name == name && s.protocol == protocol)
+                    {
+                        Some(index) => {
+                            let options = self.serves_running.remove(index);
+                            stop_serving(&options, self.processes_served.remove(index));
+                            self.serve_message = "Stopped serving";
+                        }
+                        None => self.serve_message = "Not serving this remote over that protocol",
+                    }
+                }
+                code => {
+                    let protocol = self.serve_form.value("protocol");
+                    self.serve_form.on_key(code);
+                    let new_protocol = self.serve_form.value("protocol");
+                    if protocol != new_protocol
+                        && PROTOCOLS
+                            .iter()
+                            .any(|p| default_port(p) == self.serve_form.value("port"))
+                    {
+                        self.serve_form.set("port", default_port(&new_protocol));
+                    }
+                }
+            }
+            false
+        }
+
+        fn bwlimit_keys(&mut self, key: KeyEvent) -> bool {
+            match key.code {
+                KeyCode::Esc => self.ui_idx = 0,
+                KeyCode::Enter => {
+                    let remote = self.bwlimit_form.value("remote").trim().to_owned();
+                    let global = self.bwlimit_form.value("global").trim().to_owned();
+                    if let Err(e) = validate_bwlimit(&remote).and(validate_bwlimit(&global)) {
+                        log_error(e);
+                        self.bwlimit_message = "Invalid limit, check logs for the syntax error";
+                        return false;
+                    }
+                    let i = self.drives.state.selected().unwrap();
+                    let mut options = self
+                        .settings
+                        .stored_mount_options(&self.drives.items[i].name);
+                    options.bwlimit = remote;
+                    self.settings.set_mount_options(&options);
+                    self.settings.bwlimit = global;
+                    self.save_settings();
+
+                    self.bwlimit_message = "Limits saved";
+                    for (index, drive) in self.drives_mounted.iter().enumerate() {
+                        let limit = self.settings.mount_options(&drive.name).bwlimit;
+                        match apply_bwlimit(&self.rc_mounted[index], &limit) {
+                            Ok(_) => {
+                                log_info(format!("Bandwidth of {} is now {}", drive.name, limit))
+                            }
+                            Err(e) => {
+                                log_warning(format!(
+                                    "Couldnt change bandwidth of {} live, re-mount to apply: {}",
+                                    drive.name, e
+                                ));
+                                self.bwlimit_message = "Limits saved, some mounts need a re-mount";
+                            }
+                        }
+                    }
+                }
+                code => {
+                    self.bwlimit_form.on_key(code);
+                }
+            }
+            false
+        }
+
+        /**
+         * Rescans caches in the background, the list stays until the scan is done
+         */
+        fn refresh_caches(&mut self) {
+            // a scan still running may have seen a cache that was purged since
+            self.cache_scan = Some(start_cache_scan());
+            self.cache_message = "Scanning caches ...";
+        }
+
+        /**
+         * Takes a finished scan and warns about big caches or a nearly full disk
+         */
+        fn poll_caches(&mut self) {
+            let (caches, disk) = match self.cache_scan.as_ref().map(|rx| rx.try_recv()) {
+                Some(Ok(scan)) => scan,
+                Some(Err(mpsc::TryRecvError::Empty)) | None => return,
+                Some(Err(mpsc::TryRecvError::Disconnected)) => (vec![], None),
+            };
+            self.cache_scan = None;
+            let selected = self.caches.state.selected().unwrap_or(0);
+            self.caches = StatefulList::with_items(&caches);
+            self.caches
+                .state
+                .select(Some(selected.min(caches.len().saturating_sub(1))));
+            self.cache_disk = disk;
+            if self.cache_message == "Scanning caches ..." {
+                self.cache_message = "VFS caches";
+            }
+
+            if let Some(warn) = parse_size(&self.settings.cache_warn_size) {
+                for cache in self.caches.items.iter().filter(|c| c.size > warn) {
+                    log_warning(format!(
+                        "VFS cache of {} is {}, above {}",
+                        cache.name,
+                        format_size(cache.size),
+                        self.settings.cache_warn_size
+                    ));
+                    self.cache_message = "Some caches are above the threshold";
+                }
+            }
+            if let Some((total, available)) = self.cache_disk {
+                if available < total / 10 {
+                    log_warning(format!(
+                        "Only {} left on the cache disk",
+                        format_size(available)
+                    ));
+                    self.cache_message = "The cache disk is nearly full";
+                }
+            }
+        }
+
+        fn cache_keys(&mut self, key: KeyEvent) -> bool {
+            if self.cache_insert_mode {
+                match key.code {
+                    KeyCode::Esc => self.cache_insert_mode = false,
+                    KeyCode::Backspace => {
+                        self.cache_threshold.pop();
+                    }
+                    KeyCode::Char(c) => self.cache_threshold.push(c),
+                    KeyCode::Enter => {
+                        let threshold = self.cache_threshold.trim().to_owned();
+                        if !threshold.is_empty() && parse_size(&threshold).is_none() {
+                            self.cache_message = "Threshold must be a size like 10G";
+                        } else {
+                            self.settings.cache_warn_size = threshold;
+                            self.save_settings();
+                            self.cache_insert_mode = false;
+                            self.refresh_caches();
+                        }
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
+            let confirm = self.cache_confirm;
+            self.cache_confirm = false;
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('m') => self.ui_idx = 0,
+                KeyCode::Down => self.caches.next(),
+                KeyCode::Up => self.caches.previous(),
+                KeyCode::Char('q') => return self.quit(),
+                KeyCode::Char('r') => self.refresh_caches(),
+                KeyCode::Char('t') => self.cache_insert_mode = true,
+                KeyCode::Char('x') => {
+                    let cache = match self.caches.state.selected() {
+                        Some(i) if i < self.caches.items.len() => &self.caches.items[i],
+                        _ => return false,
+                    };
+                    if self.settings.safe_mode {
+                        self.cache_message = "Safe mode, purging is disabled";
+                    } else if self.drives_mounted.iter().any(|d| d.name == cache.name) {
+                        self.cache_message = "Unmount the remote before purging its cache";
+                    } else {
+                        self.cache_confirm = true;
+                        self.cache_message = "Unsaved writes would be lost, 'y' to purge";
+                    }
+                }
+                KeyCode::Char('y') if confirm => {
+                    let i = self.caches.state.selected().unwrap();
+                    let cache = self.caches.items[i].clone();
+                    match purge_cache(&cache) {
+                        Ok(_) => {
+                            log_info(format!(
+                                "Purged {} of VFS cache for {}",
+                                format_size(cache.size),
+                                cache.name
+                            ));
+                            self.caches.items.remove(i);
+                            self.caches
+                                .state
+                                .select(Some(i.min(self.caches.items.len().saturating_sub(1))));
+                            self.refresh_caches();
+                            self.cache_message = "Cache purged";
+                        }
+                        Err(e) => {
+                            log_error(e.to_string());
+                            self.cache_message = "Couldnt purge cache, check logs";
+                        }
+                    }
+                }
+                _ => {}
+            }
+            false
+        }
+
+        fn wizard_keys(&mut self, key: KeyEvent) -> bool {
+            let provider = match self.providers.state.selected() {
+                Some(i) if i < self.providers.items.len() => self.providers.items[i].clone(),
+                _ => {
+                    if key.code == KeyCode::Esc {
+                        self.ui_idx = 1;
+                    }
+                    return false;
+                }
+            };
+            if self.wizard_stage == 0 {
+                match key.code {
+                    KeyCode::Esc => self.ui_idx = 1,
+                    KeyCode::Down => self.providers.next(),
+                    KeyCode::Up => self.providers.previous(),
+                    KeyCode::Enter => {
+                        self.wizard_form = provider_form(&provider, None);
+                        self.wizard_stage = 1;
+                        self.wizard_message = "Fill the options of the new remote";
+                    }
+                    _ => {}
+                }
+                return false;
+            }
+
+            match key.code {
+                KeyCode::Esc => {
+                    self.wizard_stage = 0;
+                    self.wizard_message = "Pick a backend for the new remote";
+                }
+                KeyCode::Enter => {
+                    let name = self.wizard_form.value(NAME_KEY).trim().to_owned();
+                    if let Err(e) = self.rclone_conf.validate_name(&name) {
+                        log_warning(format!("Invalid remote name '{}': {}", name, e));
+                        self.wizard_message = "Invalid remote name, check logs";
+                        if self.rclone_conf.drives.iter().any(|d| d.name == name) {
+                            let suggestion = self.rclone_conf.suggest_name(&name);
+                            self.wizard_form.set(NAME_KEY, &suggestion);
+                            self.wizard_message = "Name taken, a free one was suggested";
+                        }
+                        return false;
+                    }
+                    if let Err(e) = validate_provider_form(&provider, &self.wizard_form) {
+                        log_error(e);
+                        self.wizard_message = "Some options are invalid, check logs";
+                        return false;
+                    }
+                    let params = provider_params(&provider, &self.wizard_form);
+                    let oauth = provider.options.iter().any(|o| o.name == "token");
+                    if oauth && self.headless_auth {
+                        self.show_headless(&name, &provider.name, params);
+                    } else {
+                        let auth = create_remote(&name, &provider.name, params);
+                        self.show_auth(auth);
+                    }
+                }
+                code => {
+                    let sub_provider = self.wizard_form.value("provider");
+                    self.wizard_form.on_key(code);
+                    if sub_provider != self.wizard_form.value("provider") {
+                        self.wizard_form = provider_form(&provider, Some(&self.wizard_form));
+                    }
+                }
+            }
+            false
+        }
+
+        fn auth_keys(&mut self, key: KeyEvent) -> bool {
+            let auth = match self.auth.as_mut() {
+                Some(auth) => auth,
+                None => {
+                    self.ui_idx = 1;
+                    return false;
+                }
+            };
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('m') => {
+                    if !auth.status.finished() {
+                        self.drive_message = "Login continues, 'l' to follow it";
+                    }
+                    self.ui_idx = 1;
+                }
+                KeyCode::Char('x') => auth.cancel(),
+                KeyCode::Char('c') if !auth.url.is_empty() => {
+                    log_info(format!("Authorization link: {}", auth.url));
+                    let sequence = osc52(&auth.url);
+                    let backend = self.terminal.backend_mut();
+                    if let Err(e) = backend.write_all(sequence.as_bytes()).and(backend.flush()) {
+                        log_error(e.to_string());
+                    }
+                }
+                _ => {}
+            }
+            false
+        }
+
+        fn edit_keys(&mut self, key: KeyEvent) -> bool {
+            if self.open_picker(key, 10) {
+                return false;
+            }
+            let i = self.drives.state.selected().unwrap();
+            let drive = self.drives.items[i].clone();
+            match key.code {
+                KeyCode::Esc => self.ui_idx = 1,
+                KeyCode::Enter => {
+                    match changed_options(&drive, &normalize_drive_form(&self.edit_form)) {
+                        Ok(params) if params.is_empty() => self.edit_message = "Nothing changed",
+                        Ok(params) => match update_remote(&drive, params) {
+                            Ok(_) => {
+                                log_info(format!("Updated remote {}", drive.name));
+                                self.refresh_drives();
+                                self.select_drive(&drive.name);
+                                self.drive_message = "Remote updated";
+                                self.ui_idx = 1;
+                            }
+                            Err(e) => {
+                                log_error(e);
+                                self.edit_message = "rclone refused the update, check logs";
+                            }
+                        },
+                        Err(e) => {
+                            log_error(e);
+                            self.edit_message = "The new option must be key=value";
+                        }
+                    }
+                }
+                code => {
+                    self.edit_form.on_key(code);
+                }
+            }
+            false
+        }
+
+        pub fn go_edit(&mut self) {
+            let i = self.drives.state.selected().unwrap();
+            let mut help: Vec<String> = match self.edit_form.selected_field() {
+                Some(field) if !field.help.is_empty() => vec![field.help.clone()],
+                _ => vec![],
+            };
+            help.push(String::from(
+                "Secrets are masked, typing or Backspace replaces them",
+            ));
+            help.push(String::from(
+                "Enter save | Up/Down select | Esc back to drives",
+            ));
+            self.terminal
+                .draw(|f| {
+                    form_screen_ui(
+                        f,
+                        &self.edit_form,
+                        &format!(
+                            " {} ({}) ",
+                            self.drives.items[i].name, self.drives.items[i].drive_type
+                        ),
+                        self.edit_message,
+                        &help,
+                    )
+                })
+                .expect("Couldnt navigate to edit screen");
+        }
+
+        fn name_keys(&mut self, key: KeyEvent) -> bool {
+            let i = self.drives.state.selected().unwrap();
+            let name = self.drives.items[i].name.clone();
+            match key.code {
+                KeyCode::Esc => self.ui_idx = 1,
+                KeyCode::Enter => {
+                    let new_name = self.name_form.value(NEW_NAME_KEY).trim().to_owned();
+                    let in_use = self.drives_mounted.iter().any(|d| d.name == name)
+                        || self.serves_running.iter().any(|s| s.name == name);
+                    let result = match self.remote_action {
+                        RemoteAction::Rename if new_name == name => {
+                            self.ui_idx = 1;
+                            return false;
+                        }
+                        RemoteAction::Rename if in_use => {
+                            self.name_message = "Unmount and stop serving the remote first";
+                            return false;
+                        }
+                        RemoteAction::Rename => rename_remote(&name, &new_name),
+                        RemoteAction::Duplicate => duplicate_remote(&name, &new_name),
+                    };
+                    match result {
+                        Ok(_) => {
+                            match self.remote_action {
+                                RemoteAction::Rename => {
+                                    log_info(format!("Renamed remote {} to {}", name, new_name));
+                                    self.settings.rename_remote(&name, Some(&new_name));
+                                    self.save_settings();
+                                    self.drive_message = "Remote renamed";
+                                }
+                                RemoteAction::Duplicate => {
+                                    log_info(format!("Copied remote {} to {}", name, new_name));
+                                    if self.drives.items[i].token.is_some() {
+                                        log_warning(format!(
+                                            "{} shares its token with {}, refreshing one may log out the other",
+                                            new_name, name
+                                        ));
+                                    }
+                                    self.drive_message = "Remote duplicated";
+                                }
+                            }
+                            self.refresh_drives();
+                            self.select_drive(&new_name);
+                            self.ui_idx = 1;
+                        }
+                        Err(e) => {
+                            log_error(e);
+                            self.name_message = "Invalid name or rclone refused it, check logs";
+                            if self.remote_action == RemoteAction::Rename {
+                                // a rename that couldnt be undone leaves the remote under its new name
+                                self.refresh_drives();
+                                self.select_drive(&name);
+                                if self.rclone_conf.drives.iter().any(|d| d.name == new_name) {
+                                    self.settings.rename_remote(&name, Some(&new_name));
+                                    self.save_settings();
+                                    self.select_drive(&new_name);
+                                }
+                            }
+                        }
+                    }
+                }
+                code => {
+                    self.name_form.on_key(code);
+                }
+            }
+            false
+        }
+
+        pub fn go_name(&mut self) {
+            let i = self.drives.state.selected().unwrap();
+            let new_name = self.name_form.value(NEW_NAME_KEY);
+            let error = match new_name.trim() == self.drives.items[i].name {
+                true => None,
+                false => self.rclone_conf.validate_name(new_name.trim()).err(),
+            };
+            let mut help: Vec<String> = match self.name_form.selected_field() {
+                Some(field) => vec![field.help.clone()],
+                None => vec![],
+            };
+            if let Some(error) = error {
+                help.push(format!("⚠ {}", error));
+            }
+            help.push(String::from("Enter save | Esc back to drives"));
+            let title = match self.remote_action {
+                RemoteAction::Rename => format!(" Rename {} ", self.drives.items[i].name),
+                RemoteAction::Duplicate => format!(" Duplicate {} ", self.drives.items[i].name),
+            };
+            self.terminal
+                .draw(|f| form_screen_ui(f, &self.name_form, &title, self.name_message, &help))
+                .expect("Couldnt navigate to name screen");
+        }
+
+        fn crypt_keys(&mut self, key: KeyEvent) -> bool {
+            let i = self.drives.state.selected().unwrap();
+            match key.code {
+                KeyCode::Esc => self.ui_idx = 1,
+                KeyCode::Enter => {
+                    if generate_missing(&mut self.crypt_form) {
+                        self.crypt_message = "Passwords generated, back them up then press Enter";
+                        return false;
+                    }
+                    match create_crypt(&self.rclone_conf, &self.drives.items[i], &self.crypt_form) {
+                        Ok(name) => {
+                            log_info(format!(
+                                "Created crypt remote {} on {}",
+                                name, self.drives.items[i].name
+                            ));
+                            self.refresh_drives();
+                            self.select_drive(&name);
+                            self.drive_message = "Encrypted remote added";
+                            self.ui_idx = 1;
+                        }
+                        Err(e) => {
+                            log_error(e);
+                            self.crypt_message = "Couldnt create the remote, check logs";
+                        }
+                    }
+                }
+                code => {
+                    self.crypt_form.on_key(code);
+                }
+            }
+            false
+        }
+
+        pub fn go_crypt(&mut self) {
+            let i = self.drives.state.selected().unwrap();
+            let mut help: Vec<String> = match self.crypt_form.selected_field() {
+                Some(field) => vec![field.help.clone()],
+                None => vec![],
+            };
+            help.push(String::from(
+                "Lost passwords mean lost files, keep a copy outside rclone.conf",
+            ));
+            help.push(String::from(
+                "Enter create | Up/Down select | Esc back to drives",
+            ));
+            self.terminal
+                .draw(|f| {
+                    form_screen_ui(
+                        f,
+                        &self.crypt_form,
+                        &format!(" Encrypt {} ", self.drives.items[i].name),
+                        self.crypt_message,
+                        &help,
+                    )
+                })
+                .expect("Couldnt navigate to crypt screen");
+        }
+
+        fn composer_keys(&mut self, key: KeyEvent) -> bool {
+            match key.code {
+                KeyCode::Esc => self.ui_idx = 1,
+                KeyCode::Enter => match create_composed(&self.rclone_conf, &self.composer_form) {
+                    Ok(name) => {
+                        log_info(format!("Created remote {}", name));
+                        self.refresh_drives();
+                        self.select_drive(&name);
+                        self.drive_message = "Composed remote added";
+                        self.ui_idx = 1;
+                    }
+                    Err(e) => {
+                        log_error(e);
+                        self.composer_message = "Couldnt create the remote, check logs";
+                    }
+                },
+                code => {
+                    let kind = self.composer_form.value("type");
+                    self.composer_form.on_key(code);
+                    if kind != self.composer_form.value("type") {
+                        let i = self.drives.state.selected().unwrap();
+                        self.composer_form = composer_form(
+                            &self.rclone_conf,
+                            &self.drives.items[i],
+                            Some(&self.composer_form),
+                        );
+                    }
+                }
+            }
+            false
+        }
+
+        pub fn go_composer(&mut self) {
+            let mut help: Vec<String> = match self.composer_form.selected_field() {
+                Some(field) => vec![field.help.clone()],
+                None => vec![],
+            };
+            help.push(String::from(
+                "Enter create | Up/Down select | Left/Right change | Esc back to drives",
+            ));
+            self.terminal
+                .draw(|f| {
+                    form_screen_ui(
+                        f,
+                        &self.composer_form,
+                        " Compose remotes ",
+                        self.composer_message,
+                        &help,
+                    )
+                })
+                .expect("Couldnt navigate to composer screen");
+        }
+
+        fn gdrive_keys(&mut self, key: KeyEvent) -> bool {
+            if self.open_picker(key, 14) {
+                return false;
+            }
+            match key.code {
+                KeyCode::Esc => self.ui_idx = 1,
+                KeyCode::Enter => {
+                    let name = self.new_name.trim().to_owned();
+                    let service_account = self.gdrive_form.value("service_account_file");
+                    if service_account.trim().is_empty() {
+                        let params = drive_params(&self.gdrive_form);
+                        match self.headless_auth {
+                            true => self.show_headless(&name, "drive", params),
+                            false => {
+                                let auth = login_google_drive(&name, params);
+                                self.show_auth(auth);
+                            }
+                        }
+                        return false;
+                    }
+                    match validate_service_account(&self.gdrive_form) {
+                        Ok(path) => {
+                            self.gdrive_form.set("service_account_file", &path);
+                            // no browser login, the key file is the credential
+                            let mut params = drive_params(&self.gdrive_form);
+                            params.push(String::from("--non-interactive"));
+                            let auth = create_remote(&name, "drive", params);
+                            self.show_auth(auth);
+                        }
+                        Err(e) => {
+                            log_error(e);
+                            self.gdrive_message = "Invalid service account, check logs";
+                        }
+                    }
+                }
+                code => {
+                    self.gdrive_form.on_key(code);
+                }
+            }
+            false
+        }
+
+        pub fn go_gdrive(&mut self) {
+            let mut help: Vec<String> = match self.gdrive_form.selected_field() {
+                Some(field) => vec![field.help.clone()],
+                None => vec![],
+            };
+            help.push(String::from(
+                "Enter log in | Up/Down select | Left/Right change | Esc back to drives",
+            ));
+            self.terminal
+                .draw(|f| {
+                    form_screen_ui(
+                        f,
+                        &self.gdrive_form,
+                        &format!(" Adding {} (drive) ", self.new_name.trim()),
+                        self.gdrive_message,
+                        &help,
+                    )
+                })
+                .expect("Couldnt navigate to drive options screen");
+        }
+
+        /**
+         * Ctrl+O on a service account field browses for the key file
+         */
+        fn open_picker(&mut self, key: KeyEvent, from: u8) -> bool {
+            if !(key.code == KeyCode::Char('o') && key.modifiers.contains(KeyModifiers::CONTROL)) {
+                return false;
+            }
+            let form = match from {
+                10 => &self.edit_form,
+                _ => &self.gdrive_form,
+            };
+            let current = match form.selected_field() {
+                Some(field) if field.key == "service_account_file" => field.value.clone(),
+                _ => return false,
+            };
+            self.picker_return = from;
+            self.picker_message = "Pick the service account JSON file";
+            self.show_dir(start_dir(&current));
+            self.ui_idx = 15;
+            true
+        }
+
+        fn show_dir(&mut self, dir: PathBuf) {
+            match list_dir(&dir, ".json") {
+                Ok(entries) => {
+                    self.picker_entries = StatefulList::with_items(&entries);
+                    self.picker_entries.state.select(Some(0));
+                    self.picker_dir = dir;
+                }
+                Err(e) => {
+                    log_error(e);
+                    self.picker_message = "Couldnt open the folder, check logs";
+                }
+            }
+        }
+
+        fn picker_keys(&mut self, key: KeyEvent) -> bool {
+            match key.code {
+                KeyCode::Esc => self.ui_idx = self.picker_return,
+                KeyCode::Down => self.picker_entries.next(),
+                KeyCode::Up => self.picker_entries.previous(),
+                KeyCode::Backspace => {
+                    if let Some(parent) = self.picker_dir.parent() {
+                        self.show_dir(parent.to_path_buf());
+                    }
+                }
+                KeyCode::Enter => {
+                    let entry = match self.picker_entries.state.selected() {
+                        Some(i) if i < self.picker_entries.items.len() => {
+                            self.picker_entries.items[i].clone()
+                        }
+                        _ => return false,
+                    };
+                    if entry.is_dir {
+                        self.show_dir(entry.path);
+                        return false;
+                    }
+                    let path = entry.path.to_string_lossy().to_string();
+                    match self.picker_return {
+                        10 => self.edit_form.set("service_account_file", &path),
+                        _ => self.gdrive_form.set("service_account_file", &path),
+                    }
+                    self.ui_idx = self.picker_return;
+                }
+                _ => {}
+            }
+            false
+        }
+
+        pub fn go_picker(&mut self) {
+            self.terminal
+                .draw(|f| {
+                    picker_ui(
+                        f,
+                        &self.picker_entries,
+                        &self.picker_dir,
+                        self.picker_message,
+                    )
+                })
+                .expect("Couldnt navigate to picker screen");
+        }
+
+        /**
+         * Lists a folder of a remote, entries show up while rclone reads them
+         */
+        fn browse(&mut self, remote: &str, path: &str) {
+            if let Some(listing) = self.browse_listing.as_mut() {
+                listing.cancel();
+            }
+            self.browse_remote = remote.to_owned();
+            self.browse_path = path.to_owned();
+            self.browse_files = StatefulList::with_items(&[]);
+            let listing = match self.browse_filtered {
+                true => {
+                    let options = self.settings.stored_mount_options(remote);
+                    start_listing_with(remote, path, &filter_args(&options.filter))
+                }
+                false => start_listing(remote, path),
+            };
+            match listing {
+                Ok(listing) => {
+                    self.browse_listing = Some(listing);
+                    self.browse_message = "Loading ...";
+                }
+                Err(e) => {
+                    log_error(e);
+                    self.browse_listing = None;
+                    self.browse_message = "Couldnt start rclone, check logs";
+                }
+            }
+        }
+
+        fn poll_listing(&mut self) {
+            let listing = match self.browse_listing.as_mut() {
+                Some(listing) if !listing.done => listing,
+                _ => return,
+            };
+            let files = listing.poll();
+            if listing.done {
+                self.browse_message = match listing.failed {
+                    true => "Listing failed, check logs",
+                    false => "Enter open | Backspace up",
+                };
+            }
+            if files.is_empty() {
+                return;
+            }
+            self.browse_files.items.extend(files);
+            self.sort_browser();
+        }
+
+        /**
+         * Sorts the listing, keeping the cursor on the same entry
+         */
+        fn sort_browser(&mut self) {
+            let selected = self
+                .browse_files
+                .state
+                .selected()
+                .and_then(|i| self.browse_files.items.get(i))
+                .map(|f| f.name.clone());
+            sort_files(
+                &mut self.browse_files.items,
+                self.browse_sort,
+                self.browse_reverse,
+            );
+            let i = match selected {
+                Some(name) => self
+                    .browse_files
+                    .items
+                    .iter()
+                    .position(|f| f.name == name)
+                    .unwrap_or(0),
+                None => 0,
+            };
+            self.browse_files.state.select(Some(i));
+        }
+
+        fn browser_keys(&mut self, key: KeyEvent) -> bool {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('m') => {
+                    if let Some(listing) = self.browse_listing.as_mut() {
+                        listing.cancel();
+                    }
+                    self.ui_idx = 0;
+                }
+                KeyCode::Char('q') => return self.quit(),
+                KeyCode::Down => self.browse_files.next(),
+                KeyCode::Up => self.browse_files.previous(),
+                KeyCode::Enter | KeyCode::Right => {
+                    let file = match self.browse_files.state.selected() {
+                        Some(i) if i < self.browse_files.items.len() => {
+                            self.browse_files.items[i].clone()
+                        }
+                        _ => return false,
+                    };
+                    if file.is_dir {
+                        let path = child_path(&self.browse_path, &file.name);
+                        self.browse(&self.browse_remote.clone(), &path);
+                    }
+                }
+                KeyCode::Backspace | KeyCode::Left if !self.browse_path.is_empty() => {
+                    let path = parent_path(&self.browse_path);
+                    self.browse(&self.browse_remote.clone(), &path);
+                }
+                KeyCode::Char('r') => {
+                    self.browse(&self.browse_remote.clone(), &self.browse_path.clone())
+                }
+                KeyCode::Char('s') => {
+                    self.browse_sort = self.browse_sort.next();
+                    self.sort_browser();
+                }
+                KeyCode::Char('o') => {
+                    self.browse_reverse = !self.browse_reverse;
+                    self.sort_browser();
+                }
+                KeyCode::Char('u') => {
+                    if let Some(listing) = self.browse_listing.as_mut() {
+                        listing.cancel();
+                    }
+                    self.open_usage(&self.browse_remote.clone(), &self.browse_path.clone(), 16);
+                }
+                KeyCode::Char('f') => {
+                    self.browse_filtered = !self.browse_filtered;
+                    self.browse(&self.browse_remote.clone(), &self.browse_path.clone());
+                }
+                _ => {}
+            }
+            false
+        }
+
+        pub fn go_browser(&mut self) {
+            let mut crumbs = breadcrumb(&self.browse_remote, &self.browse_path);
+            if self.browse_filtered {
+                crumbs.push_str(" [mount filters]");
+            }
+            let loading = self.browse_listing.as_ref().is_some_and(|l| !l.done);
+            self.terminal
+                .draw(|f| {
+                    browser_ui(
+                        f,
+                        &self.browse_files,
+                        &crumbs,
+                        loading,
+                        self.browse_sort,
+                        self.browse_reverse,
+                        self.browse_message,
+                    )
+                })
+                .expect("Couldnt navigate to browser screen");
+        }
+
+        /**
+         * Queues an rclone command, it runs in the background once a slot is free,
+         * commands that write to a read-only remote are refused, Ok with the job id
+         */
+        fn queue_job(&mut self, label: &str, args: Vec<String>) -> Result<usize, String> {
+            if let Some(problem) = self.settings.job_problem(&args) {
+                log_warning(format!("{}, refused {}", problem, label));
+                return Err(problem);
+            }
+            // cleared jobs leave gaps, ids of unfinished ones must stay unique
+            let id = self.jobs.iter().map(|j| j.id).max().unwrap_or(0) + 1;
+            self.jobs.push(JobStruct::new(id, label, args));
+            if self.jobs_state.selected().is_none() {
+                self.jobs_state.select(Some(0));
+            }
+            self.start_queued();
+            Ok(id)
+        }
+
+        /**
+         * Starts queued jobs in order while fewer than `max_jobs` run
+         */
+        fn start_queued(&mut self) {
+            start_queued(&mut self.jobs, self.settings.max_jobs);
+        }
+
+        fn poll_jobs(&mut self) {
+            let mut finished = false;
+            for job in self.jobs.iter_mut() {
+                finished |= job.poll();
+            }
+            if finished {
+                self.start_queued();
+            }
+            for pane in self.commander_panes.iter_mut() {
+                if finished {
+                    pane.load();
+                } else {
+                    pane.poll();
+                }
+            }
+        }
+
+        /**
+         * Runs the pending operation on the selection of the active pane
+         */
+        fn run_commander_op(&mut self, op: CommanderOp) {
+            let (from, to) = match self.commander_active {
+                0 => (&self.commander_panes[0], &self.commander_panes[1]),
+                _ => (&self.commander_panes[1], &self.commander_panes[0]),
+            };
+            let file = from.selected();
+            let name = self.commander_input.trim().to_owned();
+            let args = match op_args(op, from, to, file, &name) {
+                Some(args) => args,
+                None => {
+                    self.commander_message = "Nothing selected";
+                    return;
+                }
+            };
+            let label = match args.len() {
+                3 => format!("{} {} → {}", op.label(), args[1], args[2]),
+                _ => format!("{} {}", op.label(), args[1]),
+            };
+            if self.queue_job(&label, args).is_err() {
+                self.commander_message = "That remote is read-only, nothing was changed";
+                return;
+            }
+            self.commander_message = match self.jobs.last().map(|j| j.state) {
+                Some(JobState::Failed) => "Couldnt start rclone, check logs",
+                Some(JobState::Queued) => "Job queued, 'j' shows all jobs",
+                _ => "Job started, 'j' shows all jobs",
+            };
+        }
+
+        fn commander_keys(&mut self, key: KeyEvent) -> bool {
+            let op = self.commander_op.take();
+            // mkdir and rename wait for a name
+            if let Some(op @ (CommanderOp::Mkdir | CommanderOp::Rename)) = op {
+                match key.code {
+                    KeyCode::Esc => self.commander_message = "Cancelled",
+                    KeyCode::Enter if !self.commander_input.trim().is_empty() => {
+                        self.run_commander_op(op)
+                    }
+                    KeyCode::Backspace => {
+                        self.commander_input.pop();
+                        self.commander_op = Some(op);
+                    }
+                    KeyCode::Char(c) => {
+                        self.commander_input.push(c);
+                        self.commander_op = Some(op);
+                    }
+                    _ => self.commander_op = Some(op),
+                }
+                return false;
+            }
+
+            let active = self.commander_active;
+            match key.code {
+                KeyCode::Esc => self.ui_idx = 0,
+                KeyCode::Char('q') => return self.quit(),
+                KeyCode::Tab => self.commander_active = 1 - active,
+                KeyCode::Char('j') => {
+                    self.jobs_return = 17;
+                    self.ui_idx = 18;
+                }
+                KeyCode::Down => self.commander_panes[active].files.next(),
+                KeyCode::Up => self.commander_panes[active].files.previous(),
+                KeyCode::Enter | KeyCode::Right => self.commander_panes[active].enter(),
+                KeyCode::Backspace | KeyCode::Left => self.commander_panes[active].up(),
+                KeyCode::Char('l') => self.commander_panes[active].load(),
+                KeyCode::Char('s') => {
+                    let mut sources: Vec<Option<String>> = vec![None];
+                    sources.extend(self.drives.items.iter().map(|d| Some(d.name.clone())));
+                    let current = sources
+                        .iter()
+                        .position(|s| s == &self.commander_panes[active].remote)
+                        .unwrap_or(0);
+                    let next = sources[(current + 1) % sources.len()].clone();
+                    let home = env::var("HOME").unwrap_or(String::from("/"));
+                    self.commander_panes[active].switch(next, &home);
+                }
+                KeyCode::Char('c') | KeyCode::F(5) => self.run_commander_op(CommanderOp::Copy),
+                KeyCode::Char('n') | KeyCode::F(7) => {
+                    self.commander_input.clear();
+                    self.commander_op = Some(CommanderOp::Mkdir);
+                }
+                KeyCode::Char('m' | 'x' | 'e') | KeyCode::F(6 | 8 | 2) => {
+                    if self.settings.safe_mode {
+                        self.commander_message = "Safe mode is on, only copy and mkdir work";
+                        return false;
+                    }
+                    let file = match self.commander_panes[active].selected() {
+                        Some(file) => file.clone(),
+                        None => return false,
+                    };
+                    match key.code {
+                        KeyCode::Char('e') | KeyCode::F(2) => {
+                            self.commander_input = file.name;
+                            self.commander_op = Some(CommanderOp::Rename);
+                        }
+                        KeyCode::Char('m') | KeyCode::F(6) => {
+                            self.commander_op = Some(CommanderOp::Move);
+                            self.commander_message = "Move removes the source, 'y' to confirm";
+                        }
+                        _ => {
+                            self.commander_op = Some(CommanderOp::Delete);
+                            self.commander_message = match file.is_dir {
+                                true => "Delete the folder and everything in it? 'y' to confirm",
+                                false => "Delete this file? 'y' to confirm",
+                            };
+                        }
+                    }
+                }
+                KeyCode::Char('y') => match op {
+                    Some(op) => self.run_commander_op(op),
+                    None => self.commander_message = "Nothing to confirm",
+                },
+                _ => {}
+            }
+            if op.is_some() && self.commander_op.is_none() && key.code != KeyCode::Char('y') {
+                self.commander_message = "Cancelled";
+            }
+            false
+        }
+
+        fn jobs_keys(&mut self, key: KeyEvent) -> bool {
+            let selected = self.jobs_state.selected().filter(|i| *i < self.jobs.len());
+            match key.code {
+                KeyCode::Esc => self.ui_idx = self.jobs_return,
+                KeyCode::Char('q') => return self.quit(),
+                KeyCode::Down if !self.jobs.is_empty() => {
+                    let i = selected.map_or(0, |i| (i + 1) % self.jobs.len());
+                    self.jobs_state.select(Some(i));
+                }
+                KeyCode::Up if !self.jobs.is_empty() => {
+                    let i = selected.map_or(0, |i| (i + self.jobs.len() - 1) % self.jobs.len());
+                    self.jobs_state.select(Some(i));
+                }
+                KeyCode::Char('x') => match selected {
+                    Some(i) if !self.jobs[i].state.finished() => {
+                        self.jobs[i].cancel();
+                        self.start_queued();
+                        self.jobs_message = "Job cancelled";
+                    }
+                    _ => self.jobs_message = "Only queued or running jobs can be cancelled",
+                },
+                KeyCode::Char('r') => {
+                    // safe mode or a read-only flag may have been set since it first ran
+                    let problem =
+                        selected.and_then(|i| self.settings.job_problem(&self.jobs[i].args));
+                    match (selected, problem) {
+                        (Some(i), Some(problem)) => {
+                            log_warning(format!("{}, refused {}", problem, self.jobs[i].label));
+                            self.jobs_message = "Safe mode or a read-only remote blocks this job";
+                        }
+                        (Some(i), None) if self.jobs[i].retry() => {
+                            self.start_queued();
+                            self.jobs_message = "Job queued again";
+                        }
+                        _ => self.jobs_message = "Only failed or cancelled jobs can be retried",
+                    }
+                }
+                KeyCode::Char('c') => {
+                    self.jobs.retain(|j| !j.state.finished());
+                    self.jobs_state.select(match self.jobs.is_empty() {
+                        true => None,
+                        false => Some(0),
+                    });
+                    self.jobs_message = "Finished jobs cleared";
+                }
+                KeyCode::Char('h') => self.open_history(18),
+                KeyCode::Char('+') => {
+                    self.settings.max_jobs += 1;
+                    self.save_settings();
+                    self.start_queued();
+                    self.jobs_message = "Parallel jobs saved";
+                }
+                KeyCode::Char('-') if self.settings.max_jobs > 1 => {
+                    // running jobs go on, only new ones wait
+                    self.settings.max_jobs -= 1;
+                    self.save_settings();
+                    self.jobs_message = "Parallel jobs saved";
+                }
+                _ => {}
+            }
+            false
+        }
+
+        /**
+         * Queues scheduled sync jobs that are due, missed ones on the first tick
+         */
+        fn run_schedules(&mut self) {
+            let now = now();
+            if now - self.schedule_checked < TICK {
+                return;
+            }
+            self.schedule_checked = now;
+            // a running daemon owns the schedules, this window only shows them
+            if !self.scheduler.as_ref().is_some_and(|lock| lock.held()) {
+                self.scheduler = None;
+                match SchedulerLockStruct::acquire("tui", &[]) {
+                    Ok(lock) => {
+                        if self.schedules_deferred {
+                            log_info(String::from("Schedules run in this window again"));
+                        }
+                        self.scheduler = Some(lock);
+                        self.schedules_deferred = false;
+                    }
+                    Err(holder) => {
+                        if !self.schedules_deferred {
+                            log_info(format!("Schedules are run by {}", holder));
+                        }
+                        self.schedules_deferred = true;
+                        self.settings.merge_run_times(&read_rrclone_config());
+                        return;
+                    }
+                }
+            }
+            self.settings.merge_run_times(&read_rrclone_config());
+            let before = self.settings.syncs.clone();
+            for job in due_jobs(&mut self.settings, now) {
+                let label = sync_label(&job);
+                if self
+                    .jobs
+                    .iter()
+                    .any(|j| j.label == label && !j.state.finished())
+                {
+                    log_warning(format!("{} is still running, skipped", job.name));
+                    continue;
+                }
+                log_info(format!("Scheduled run of {}", job.name));
+                let _ = self.queue_job(&label, sync_args(&job));
+            }
+            if self.settings.syncs != before {
+                self.save_settings();
+            }
+        }
+
+        fn selected_sync(&self) -> Option<SyncJobStruct> {
+            self.syncs_state
+                .selected()
+                .and_then(|i| self.settings.syncs.get(i))
+                .cloned()
+        }
+
+        fn sync_keys(&mut self, key: KeyEvent) -> bool {
+            let confirm = self.sync_confirm;
+            self.sync_confirm = false;
+            let selected = self.selected_sync();
+            let count = self.settings.syncs.len();
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('m') => self.ui_idx = 0,
+                KeyCode::Char('q') => return self.quit(),
+                KeyCode::Char('j') => {
+                    self.jobs_return = 19;
+                    self.ui_idx = 18;
+                }
+                KeyCode::Down if count > 0 => {
+                    let i = self.syncs_state.selected().map_or(0, |i| (i + 1) % count);
+                    self.syncs_state.select(Some(i));
+                }
+                KeyCode::Up if count > 0 => {
+                    let i = self
+                        .syncs_state
+                        .selected()
+                        .map_or(0, |i| (i + count - 1) % count);
+                    self.syncs_state.select(Some(i));
+                }
+                KeyCode::Char('i') => {
+                    self.sync_form = sync_form(None);
+                    self.sync_editing = None;
+                    self.sync_form_message = "New sync job";
+                    self.ui_idx = 20;
+                }
+                KeyCode::Char('e') => {
+                    if let Some(job) = selected {
+                        self.sync_form = sync_form(Some(&job));
+                        self.sync_editing = Some(job.name);
+                        self.sync_form_message = "Edit sync job";
+                        self.ui_idx = 20;
+                    }
+                }
+                KeyCode::Char('f') => {
+                    if let Some(job) = selected {
+                        self.open_filter(
+                            FilterTarget::Sync(job.name.clone()),
+                            &job.filter,
+                            &job.source,
+                            19,
+                        );
+                    }
+                }
+                KeyCode::Char('x') if selected.is_some() => {
+                    self.sync_confirm = true;
+                    self.sync_message = "Delete this sync job? 'y' to confirm";
+                }
+                KeyCode::Char('y') if confirm => {
+                    if let Some(i) = self.syncs_state.selected() {
+                        self.settings.syncs.remove(i);
+                        self.save_settings();
+                        self.syncs_state
+                            .select(match self.settings.syncs.is_empty() {
+                                true => None,
+                                false => Some(i.saturating_sub(1)),
+                            });
+                        self.sync_message = "Sync job deleted";
+                    }
+                }
+                KeyCode::Enter => {
+                    let job = match selected {
+                        Some(job) => job,
+                        None => return false,
+                    };
+                    if let Some(preview) = self.sync_preview.as_mut() {
+                        preview.cancel();
+                    }
+                    self.sync_preview = match start_preview(&job) {
+                        Ok(preview) => {
+                            self.sync_message = "Dry run started, nothing is changed yet";
+                            Some(preview)
+                        }
+                        Err(e) => {
+                            log_error(e);
+                            self.sync_message = "Couldnt start rclone, check logs";
+                            None
+                        }
+                    };
+                }
+                KeyCode::Char('y') => {
+                    let job = match selected {
+                        Some(job) => job,
+                        None => return false,
+                    };
+                    let previewed = self.sync_preview.as_ref().map(|p| {
+                        (
+                            p.name == job.name && p.args == sync_args(&job),
+                            p.done,
+                            p.failed,
+                        )
+                    });
+                    self.sync_message = match previewed {
+                        Some((true, true, false)) if deletes(&job) && self.settings.safe_mode => {
+                            "Safe mode is on, only copy jobs run"
+                        }
+                        Some((true, true, false))
+                            if self.queue_job(&sync_label(&job), sync_args(&job)).is_err() =>
+                        {
+                            "The destination is read-only, the sync was refused"
+                        }
+                        Some((true, true, false)) => {
+                            if let Some(i) = self.syncs_state.selected() {
+                                self.settings.syncs[i].last_run = Some(now());
+                                self.save_settings();
+                            }
+                            self.sync_preview = None;
+                            "Sync queued, 'j' shows its progress"
+                        }
+                        Some((true, false, _)) => "Wait for the dry run to finish",
+                        Some((true, true, true)) => "The dry run failed, check logs",
+                        _ => "Press Enter for a dry run first",
+                    };
+                }
+                _ => {}
+            }
+            false
+        }
+
+        pub fn go_sync(&mut self) {
+            let preview = self.sync_preview.as_ref();
+            self.terminal
+                .draw(|f| {
+                    sync_ui(
+                        f,
+                        &self.settings.syncs,
+                        &self.syncs_state,
+                        preview,
+                        self.sync_message,
+                    )
+                })
+                .expect("Couldnt navigate to sync screen");
+        }
+
+        fn sync_form_keys(&mut self, key: KeyEvent) -> bool {
+            match key.code {
+                KeyCode::Esc => self.ui_idx = 19,
+                KeyCode::Enter => {
+                    let editing = self.sync_editing.clone();
+                    let mut job = match sync_from_form(
+                        &self.sync_form,
+                        &self.settings.syncs,
+                        editing.as_deref(),
+                    ) {
+                        Ok(job) => job,
+                        Err(e) => {
+                            log_error(e);
+                            self.sync_form_message = "Invalid sync job, check logs";
+                            return false;
+                        }
+                    };
+                    let previous = self
+                        .settings
+                        .syncs
+                        .iter()
+                        .find(|s| Some(&s.name) == self.sync_editing.as_ref())
+                        .cloned();
+                    reschedule(&mut job, previous.as_ref(), now());
+                    if let Err(e) = self.settings.set_sync(&job, editing.as_deref()) {
+                        log_error(e);
+                        self.sync_form_message = "A sync job with that name exists";
+                        return false;
+                    }
+                    self.save_settings();
+                    // the dry run showed the job as it was
+                    if let Some(mut preview) = self.sync_preview.take() {
+                        preview.cancel();
+                    }
+                    let i = self.settings.syncs.iter().position(|s| s.name == job.name);
+                    self.syncs_state.select(i);
+                    self.sync_message = "Sync job saved, Enter for a dry run";
+                    self.ui_idx = 19;
+                }
+                code => {
+                    self.sync_form.on_key(code);
+                }
+            }
+            false
+        }
+
+        pub fn go_sync_form(&mut self) {
+            let mut help: Vec<String> = match self.sync_form.selected_field() {
+                Some(field) => vec![field.help.clone()],
+                None => vec![],
+            };
+            help.push(String::from("Enter save | Esc back to sync jobs"));
+            self.terminal
+                .draw(|f| {
+                    form_screen_ui(
+                        f,
+                        &self.sync_form,
+                        " Sync job ",
+                        self.sync_form_message,
+                        &help,
+                    )
+                })
+                .expect("Couldnt navigate to sync form screen");
+        }
+
+        /**
+         * Opens the rule editor of a mount or a sync job and previews its rules
+         */
+        fn open_filter(
+            &mut self,
+            target: FilterTarget,
+            set: &FilterSetStruct,
+            preview_dir: &str,
+            from: u8,
+        ) {
+            let mut editor = FilterEditorStruct::new(target, set, preview_dir);
+            self.filter_message = match editor.refresh_preview() {
+                Ok(()) => String::from("Rules are checked in order, the first match wins"),
+                Err(e) => e,
+            };
+            self.filter_editor = Some(editor);
+            self.filter_return = from;
+            self.ui_idx = 23;
+        }
+
+        fn close_filter(&mut self) {
+            if let Some(preview) = self.filter_editor.as_mut().and_then(|e| e.preview.as_mut()) {
+                preview.cancel();
+            }
+            self.filter_editor = None;
+            self.ui_idx = self.filter_return;
+        }
+
+        /**
+         * Stores the rules where they came from, only when rclone will accept them
+         */
+        fn save_filter(&mut self) {
+            let editor = match self.filter_editor.as_ref() {
+                Some(editor) => editor,
+                None => return,
+            };
+            if let Some(problem) = editor.problems().first() {
+                self.filter_message = format!("Not saved: {}", problem);
+                return;
+            }
+            let set = editor.set();
+            let title = editor.title();
+            match editor.target.clone() {
+                FilterTarget::Mount(name) => {
+                    let mut options = self.settings.stored_mount_options(&name);
+                    options.filter = set;
+                    self.settings.set_mount_options(&options);
+                    self.main_message = match self.drives_mounted.iter().any(|d| d.name == name) {
+                        true => "Filters saved, re-mount to apply",
+                        false => "Filters saved",
+                    };
+                }
+                FilterTarget::Sync(name) => {
+                    if let Some(job) = self.settings.syncs.iter_mut().find(|s| s.name == name) {
+                        job.filter = set;
+                    }
+                    if let Some(mut preview) = self.sync_preview.take() {
+                        preview.cancel();
+                    }
+                    self.sync_message = "Filters saved, Enter for a dry run";
+                }
+            }
+            self.save_settings();
+            log_info(format!("{} saved", title));
+            self.close_filter();
+        }
+
+        fn filter_keys(&mut self, key: KeyEvent) -> bool {
+            let editor = match self.filter_editor.as_mut() {
+                Some(editor) => editor,
+                None => {
+                    self.ui_idx = self.filter_return;
+                    return false;
+                }
+            };
+            if editor.input.is_some() {
+                match key.code {
+                    KeyCode::Esc => editor.input = None,
+                    KeyCode::Enter => {
+                        if let Err(e) = editor.submit() {
+                            self.filter_message = e;
+                        }
+                    }
+                    KeyCode::Backspace => {
+                        editor.input_text.pop();
+                    }
+                    KeyCode::Char(c) => editor.input_text.push(c),
+                    _ => {}
+                }
+                return false;
+            }
+            let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+            match (editor.focus_rules, key.code) {
+                (_, KeyCode::Esc) => self.close_filter(),
+                (_, KeyCode::Tab) => editor.focus_rules = !editor.focus_rules,
+                (true, KeyCode::Char('s')) => self.save_filter(),
+                (false, KeyCode::Char('s')) if ctrl => self.save_filter(),
+                (_, KeyCode::Char('r')) if editor.focus_rules || ctrl => {
+                    if let Err(e) = editor.refresh_preview() {
+                        self.filter_message = e;
+                    }
+                }
+                (true, KeyCode::Char('q')) => return self.quit(),
+                (true, KeyCode::Down) => editor.rules.next(),
+                (true, KeyCode::Up) => editor.rules.previous(),
+                (true, KeyCode::Char(c @ ('+' | '-' | 'p'))) => {
+                    editor.input = Some(match c {
+                        '+' => FilterInput::Include,
+                        '-' => FilterInput::Exclude,
+                        _ => FilterInput::PreviewDir,
+                    });
+                    editor.input_text = match c {
+                        'p' => editor.preview_dir.clone(),
+                        _ => String::new(),
+                    };
+                }
+                (true, KeyCode::Enter) => {
+                    if let Some(i) = editor.rules.state.selected() {
+                        editor.input_text = editor.rules.items[i].clone();
+                        editor.input = Some(FilterInput::Edit(i));
+                    }
+                }
+                (true, KeyCode::Char('!')) => {
+                    editor.input_text = String::from("!");
+                    editor.input = Some(FilterInput::Include);
+                    if let Err(e) = editor.submit() {
+                        self.filter_message = e;
+                    }
+                }
+                (true, KeyCode::Char('x')) => {
+                    editor.remove_rule();
+                    if let Err(e) = editor.refresh_preview() {
+                        self.filter_message = e;
+                    }
+                }
+                (true, KeyCode::Char(c @ ('K' | 'J'))) => {
+                    editor.move_rule(c == 'K');
+                    if let Err(e) = editor.refresh_preview() {
+                        self.filter_message = e;
+                    }
+                }
+                (false, code) => {
+                    editor.form.on_key(code);
+                }
+                _ => {}
+            }
+            false
+        }
+
+        pub fn go_filter(&mut self) {
+            let editor = match self.filter_editor.as_ref() {
+                Some(editor) => editor,
+                None => return,
+            };
+            self.terminal
+                .draw(|f| filter_ui(f, editor, &self.filter_message))
+                .expect("Couldnt navigate to filter screen");
+        }
+
+        /**
+         * Scans a folder of a remote for its sizes, the tree fills while rclone lists
+         */
+        fn open_usage(&mut self, remote: &str, root: &str, from: u8) {
+            if let Some(usage) = self.usage.as_mut() {
+                usage.cancel();
+            }
+            self.usage = match UsageScanStruct::start(remote, root) {
+                Ok(usage) => {
+                    self.usage_message = "Scanning, sizes grow as files are found";
+                    Some(usage)
+                }
+                Err(e) => {
+                    log_error(e);
+                    self.main_message = "Couldnt start rclone, check logs";
+                    return;
+                }
+            };
+            self.usage_dir = String::new();
+            self.usage_state.select(Some(0));
+            self.usage_confirm = false;
+            self.usage_return = from;
+            self.ui_idx = 24;
+        }
+
+        /**
+         * Drops entries from the tree once their delete job is done, failed ones stay
+         */
+        fn poll_usage_deletes(&mut self) {
+            let usage = match self.usage.as_mut() {
+                Some(usage) if !usage.deleting.is_empty() => usage,
+                _ => return,
+            };
+            let jobs = &self.jobs;
+            let mut failed = false;
+            usage.deleting.retain(|(id, path)| {
+                match jobs.iter().find(|j| j.id == *id).map(|j| j.state) {
+                    Some(JobState::Queued | JobState::Running) => true,
+                    Some(JobState::Done) => {
+                        usage.tree.remove(path);
+                        false
+                    }
+                    _ => {
+                        failed = true;
+                        false
+                    }
+                }
+            });
+            if failed {
+                self.usage_message = "A delete failed, its entry stays, check logs";
+            }
+            let count = self.usage_entries().len();
+            if self.usage_state.selected().is_some_and(|i| i >= count) {
+                self.usage_state.select(Some(count.saturating_sub(1)));
+            }
+        }
+
+        fn usage_entries(&self) -> Vec<UsageEntryStruct> {
+            match self.usage.as_ref() {
+                Some(usage) => usage.tree.entries(&self.usage_dir, self.usage_sort),
+                None => vec![],
+            }
+        }
+
+        fn usage_keys(&mut self, key: KeyEvent) -> bool {
+            let confirm = self.usage_confirm;
+            self.usage_confirm = false;
+            let entries = self.usage_entries();
+            let selected = self
+                .usage_state
+                .selected()
+                .and_then(|i| entries.get(i))
+                .cloned();
+            let count = entries.len();
+            match key.code {
+                KeyCode::Esc => {
+                    if let Some(usage) = self.usage.as_mut() {
+                        usage.cancel();
+                    }
+                    self.usage = None;
+                    self.ui_idx = self.usage_return;
+                    // the browser listing was stopped for the scan
+                    if self.ui_idx == 16 {
+                        self.browse(&self.browse_remote.clone(), &self.browse_path.clone());
+                    }
+                }
+                KeyCode::Char('q') => return self.quit(),
+                KeyCode::Char('j') => {
+                    self.jobs_return = 24;
+                    self.ui_idx = 18;
+                }
+                KeyCode::Down if count > 0 => {
+                    let i = self.usage_state.selected().map_or(0, |i| (i + 1) % count);
+                    self.usage_state.select(Some(i));
+                }
+                KeyCode::Up if count > 0 => {
+                    let i = self
+                        .usage_state
+                        .selected()
+                        .map_or(0, |i| (i + count - 1) % count);
+                    self.usage_state.select(Some(i));
+                }
+                KeyCode::Enter | KeyCode::Right => {
+                    if let Some(entry) = selected.filter(|e| e.is_dir) {
+                        self.usage_dir = entry.path;
+                        self.usage_state.select(Some(0));
+                    }
+                }
+                KeyCode::Backspace | KeyCode::Left if !self.usage_dir.is_empty() => {
+                    let dir = self.usage_dir.clone();
+                    self.usage_dir = parent_path(&dir);
+                    // back on the folder we came from
+                    let i = self.usage_entries().iter().position(|e| e.path == dir);
+                    self.usage_state.select(Some(i.unwrap_or(0)));
+                }
+                KeyCode::Char('s') => {
+                    self.usage_sort = self.usage_sort.next();
+                    self.usage_state.select(Some(0));
+                }
+                KeyCode::Char('c') => {
+                    if let Some(usage) = self.usage.as_mut().filter(|u| !u.done()) {
+                        usage.cancel();
+                        self.usage_message = "Scan cancelled";
+                    }
+                }
+                KeyCode::Char('r') => {
+                    if let Some(usage) = self.usage.as_ref() {
+                        let (remote, root) = (usage.remote.clone(), usage.root.clone());
+                        let dir = self.usage_dir.clone();
+                        self.open_usage(&remote, &root, self.usage_return);
+                        self.usage_dir = dir;
+                    }
+                }
+                KeyCode::Char('x') => {
+                    let entry = match selected {
+                        Some(entry) => entry,
+                        None => return false,
+                    };
+                    if self.settings.safe_mode {
+                        self.usage_message = "Safe mode is on, deleting is disabled";
+                        return false;
+                    }
+                    self.usage_confirm = true;
+                    self.usage_message = match entry.is_dir {
+                        true => "Delete the folder and everything in it? 'y' to confirm",
+                        false => "Delete this file? 'y' to confirm",
+                    };
+                }
+                KeyCode::Char('y') if confirm => {
+                    let (entry, usage) = match (selected, self.usage.as_ref()) {
+                        (Some(entry), Some(usage)) => (entry, usage),
+                        _ => return false,
+                    };
+                    let args = usage.delete_args(&entry);
+                    let label = format!("delete {}", args[1]);
+                    let id = match self.queue_job(&label, args) {
+                        Ok(id) => id,
+                        Err(_) => {
+                            self.usage_message = "This remote is read-only, nothing was deleted";
+                            return false;
+                        }
+                    };
+                    if let Some(usage) = self.usage.as_mut() {
+                        usage.deleting.push((id, entry.path));
+                    }
+                    self.usage_message = match self.jobs.last().map(|j| j.state) {
+                        Some(JobState::Failed) => "Couldnt start rclone, check logs",
+                        _ => "Delete queued, the entry goes once it is done",
+                    };
+                }
+                _ => {}
+            }
+            if confirm && !self.usage_confirm && key.code != KeyCode::Char('y') {
+                self.usage_message = "Cancelled";
+            }
+            false
+        }
+
+        pub fn go_usage(&mut self) {
+            let usage = match self.usage.as_ref() {
+                Some(usage) => usage,
+                None => return,
+            };
+            let entries = usage.tree.entries(&self.usage_dir, self.usage_sort);
+            self.terminal
+                .draw(|f| {
+                    usage_ui(
+                        f,
+                        usage,
+                        &self.usage_dir,
+                        &entries,
+                        &self.usage_state,
+                        self.usage_sort,
+                        self.usage_message,
+                    )
+                })
+                .expect("Couldnt navigate to usage screen");
+        }
+
+        fn open_history(&mut self, from: u8) {
+            self.history = read_history();
+            self.history_state
+                .select((!self.history.is_empty()).then_some(0));
+            self.history_return = from;
+            self.ui_idx = 21;
+        }
+
+        /**
+         * History entries the filter keeps, the list selection indexes this
+         */
+        fn filtered_history(&self) -> Vec<&HistoryEntryStruct> {
+            self.history
+                .iter()
+                .filter(|e| e.matches(&self.history_filter))
+                .collect()
+        }
+
+        fn history_keys(&mut self, key: KeyEvent) -> bool {
+            if self.history_filtering {
+                match key.code {
+                    KeyCode::Esc => {
+                        self.history_filter.clear();
+                        self.history_filtering = false;
+                    }
+                    KeyCode::Enter => self.history_filtering = false,
+                    KeyCode::Backspace => {
+                        self.history_filter.pop();
+                    }
+                    KeyCode::Char(c) => self.history_filter.push(c),
+                    _ => return false,
+                }
+                let empty = self.filtered_history().is_empty();
+                self.history_state.select((!empty).then_some(0));
+                return false;
+            }
+            let count = self.filtered_history().len();
+            let selected = self.history_state.selected().filter(|i| *i < count);
+            match key.code {
+                KeyCode::Esc => self.ui_idx = self.history_return,
+                KeyCode::Char('q') => return self.quit(),
+                KeyCode::Char('/') => self.history_filtering = true,
+                KeyCode::Char('r') => self.open_history(self.history_return),
+                KeyCode::Down if count > 0 => {
+                    let i = selected.map_or(0, |i| (i + 1) % count);
+                    self.history_state.select(Some(i));
+                }
+                KeyCode::Up if count > 0 => {
+                    let i = selected.map_or(0, |i| (i + count - 1) % count);
+                    self.history_state.select(Some(i));
+                }
+                KeyCode::Enter => {
+                    if let Some(i) = selected {
+                        let entry = self.filtered_history()[i];
+                        self.history_log = match entry.log_path.is_empty() {
+                            true => vec![String::from("This job has no log file")],
+                            false => read_log(entry),
+                        };
+                        self.history_scroll = 0;
+                        self.ui_idx = 22;
+                    }
+                }
+                KeyCode::Char(c @ ('J' | 'M')) => {
+                    let format = match c {
+                        'J' => ReportFormat::Json,
+                        _ => ReportFormat::Markdown,
+                    };
+                    if let Some(i) = selected {
+                        self.history_message =
+                            match export_report(self.filtered_history()[i], format) {
+                                Ok(path) => format!("Report written to {}", path.display()),
+                                Err(e) => {
+                                    log_error(e);
+                                    String::from("Couldnt write the report, check logs")
+                                }
+                            };
+                    }
+                }
+                _ => {}
+            }
+            false
+        }
+
+        pub fn go_history(&mut self) {
+            // field by field, the terminal is borrowed mutably below
+            let entries: Vec<&HistoryEntryStruct> = self
+                .history
+                .iter()
+                .filter(|e| e.matches(&self.history_filter))
+                .collect();
+            let filter = match self.history_filtering {
+                true => Some(self.history_filter.as_str()),
+                false => None,
+            };
+            let message = match self.history_filter.is_empty() {
+                true => self.history_message.clone(),
+                false => format!(
+                    "{} | filtered by '{}'",
+                    self.history_message, self.history_filter
+                ),
+            };
+            self.terminal
+                .draw(|f| history_ui(f, &entries, &self.history_state, filter, &message))
+                .expect("Couldnt navigate to history screen");
+        }
+
+        fn log_keys(&mut self, key: KeyEvent) -> bool {
+            let last = self.history_log.len().saturating_sub(1) as u16;
+            self.history_scroll = match key.code {
+                KeyCode::Esc => {
+                    self.ui_idx = 21;
+                    0
+                }
+                KeyCode::Char('q') => return self.quit(),
+                KeyCode::Down => self.history_scroll.saturating_add(1),
+                KeyCode::Up => self.history_scroll.saturating_sub(1),
+                KeyCode::PageDown => self.history_scroll.saturating_add(20),
+                KeyCode::PageUp => self.history_scroll.saturating_sub(20),
+                KeyCode::Home => 0,
+                KeyCode::End => last,
+                _ => self.history_scroll,
+            }
+            .min(last);
+            false
+        }
+
+        pub fn go_log(&mut self) {
+            let title = match self
+                .filtered_history()
+                .get(self.history_state.selected().unwrap_or(0))
+            {
+                Some(entry) => entry.label.clone(),
+                None => String::from("Log"),
+            };
+            self.terminal
+                .draw(|f| log_ui(f, &title, &self.history_log, self.history_scroll))
+                .expect("Couldnt navigate to log screen");
+        }
+
+        pub fn go_jobs(&mut self) {
+            self.terminal
+                .draw(|f| {
+                    jobs_ui(
+                        f,
+                        &self.jobs,
+                        &self.jobs_state,
+                        self.settings.max_jobs,
+                        self.jobs_message,
+                    )
+                })
+                .expect("Couldnt navigate to jobs screen");
+        }
+
+        pub fn go_commander(&mut self) {
+            let input = match self.commander_op {
+                Some(CommanderOp::Mkdir) => Some(("New folder", self.commander_input.as_str())),
+                Some(CommanderOp::Rename) => Some(("New name", self.commander_input.as_str())),
+                _ => None,
+            };
+            self.terminal
+                .draw(|f| {
+                    commander_ui(
+                        f,
+                        &self.commander_panes[0],
+                        &self.commander_panes[1],
+                        self.commander_active,
+                        &self.jobs,
+                        input,
+                        self.commander_message,
+                    )
+                })
+                .expect("Couldnt navigate to commander screen");
+        }
+
+        fn headless_keys(&mut self, key: KeyEvent) -> bool {
+            match key.code {
+                KeyCode::Esc => self.ui_idx = 1,
+                KeyCode::Backspace => {
+                    self.headless.token.clear();
+                    self.headless_message = "Token cleared";
+                }
+                KeyCode::Char(c) => self.headless.token.push(c),
+                KeyCode::Enter => match validate_token(&self.headless.token) {
+                    Ok(token) => {
+                        let auth = create_with_token(
+                            &self.headless.name,
+                            &self.headless.backend,
+                            self.headless.params.clone(),
+                            &token,
+                        );
+                        self.show_auth(auth);
+                    }
+                    Err(e) => {
+                        log_error(format!("Pasted token rejected: {}", e));
+                        self.headless_message = "Invalid token, check logs";
+                    }
+                },
+                _ => {}
+            }
+            false
+        }
+
+        pub fn go_headless(&mut self) {
+            self.terminal
+                .draw(|f| headless_ui(f, &self.headless, self.headless_message))
+                .expect("Couldnt navigate to headless screen");
+        }
+
+        pub fn go_auth(&mut self) {
+            if let Some(auth) = &self.auth {
+                self.terminal
+                    .draw(|f| auth_ui(f, auth, auth.status.label()))
+                    .expect("Couldnt navigate to auth screen");
+            }
+        }
+
+        pub fn go_wizard(&mut self) {
+            self.terminal
+                .draw(|f| {
+                    wizard_ui(
+                        f,
+                        &self.providers,
+                        &self.wizard_form,
+                        self.wizard_stage,
+                        self.wizard_message,
+                    )
+                })
+                .expect("Couldnt navigate to wizard screen");
+        }
+
+        pub fn go_cache(&mut self) {
+            let mounted: Vec<String> = self.drives_mounted.iter().map(|d| d.name.clone()).collect();
+            let warn_size = parse_size(&self.settings.cache_warn_size);
+            self.terminal
+                .draw(|f| {
+                    cache_ui(
+                        f,
+                        &self.caches,
+                        &mounted,
+                        warn_size,
+                        self.cache_disk,
+                        &self.cache_threshold,
+                        self.cache_insert_mode,
+                        self.cache_message,
+                    )
+                })
+                .expect("Couldnt navigate to cache screen");
+        }
+
+        pub fn go_bwlimit(&mut self) {
+            let i = self.drives.state.selected().unwrap();
+            let help = vec![
+                String::from("A rate: 10M, 512k, off"),
+                String::from("Upload:download: 10M:100M, off:1M"),
+                String::from("Timetable: 08:00,512k 12:00,10M Sat-00:00,off"),
+                String::from("Timetables apply when mounting, rates change running mounts"),
+                String::from("Enter save | Up/Down select | Esc back to main"),
+            ];
+            self.terminal
+                .draw(|f| {
+                    form_screen_ui(
+                        f,
+                        &self.bwlimit_form,
+                        &format!(" Bandwidth of {} ", self.drives.items[i].name),
+                        self.bwlimit_message,
+                        &help,
+                    )
+                })
+                .expect("Couldnt navigate to bandwidth screen");
+        }
+
+        pub fn go_serve(&mut self) {
+            let i = self.drives.state.selected().unwrap();
+            self.terminal
+                .draw(|f| {
+                    serve_ui(
+                        f,
+                        &self.drives.items[i].name,
+                        &self.serve_form,
+                        &self.serves_running,
+                        self.serve_message,
+                    )
+                })
+                .expect("Couldnt navigate to serve screen");
+        }
+
         pub fn go_error(&mut self, width: u16, height: u16) {
             self.terminal
                 .draw(|f| error_ui(f, width, height))
@@ -0,0 +1,315 @@
+#![allow(dead_code)]
+
+pub mod auth {
+    use std::{
+        env,
+        io::{BufRead, BufReader, Read, Write},
+        process::{Child, Command, Stdio},
+        sync::mpsc::{self, Receiver, Sender},
+        thread,
+    };
+
+    use chrono::DateTime;
+    use serde_json::{Map, Value};
+
+    use crate::lib::utils::utils::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum AuthStatus {
+        Starting,
+        WaitingForBrowser,
+        GotCode,
+        Success,
+        Failed,
+        Cancelled,
+    }
+
+    impl AuthStatus {
+        pub fn label(&self) -> &'static str {
+            match self {
+                AuthStatus::Starting => "Starting rclone ...",
+                AuthStatus::WaitingForBrowser => "Waiting for authorization in the browser",
+                AuthStatus::GotCode => "Got the code, saving the remote ...",
+                AuthStatus::Success => "Remote created, drives reloaded",
+                AuthStatus::Failed => "rclone failed, check the output",
+                AuthStatus::Cancelled => "Login cancelled",
+            }
+        }
+
+        pub fn finished(&self) -> bool {
+            matches!(
+                self,
+                AuthStatus::Success | AuthStatus::Failed | AuthStatus::Cancelled
+            )
+        }
+    }
+
+    /**
+     * A remote waiting for a token pasted from `rclone authorize`
+     */
+    #[derive(Debug, Clone, Default)]
+    pub struct HeadlessStruct {
+        pub name: String,
+        pub backend: String,
+        pub params: Vec<String>,
+        pub token: String,
+    }
+
+    pub struct AuthStruct {
+        pub name: String,
+        pub status: AuthStatus,
+        pub url: String,
+        pub lines: Vec<String>,
+        process: Child,
+        rx: Receiver<String>,
+    }
+
+    fn forward_lines<R: Read + Send + 'static>(pipe: Option<R>, tx: Sender<String>) {
+        if let Some(pipe) = pipe {
+            thread::spawn(move || {
+                for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+                    if tx.send(line).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+    }
+
+    /**
+     * Starts `rclone <args>` with its output captured line by line
+     */
+    pub fn start_auth(name: &str, args: Vec<String>) -> Result<AuthStruct, String> {
+        start_auth_with(name, args, None)
+    }
+
+    /**
+     * Same with answers written to the prompts of rclone, secrets go there instead of `ps`
+     */
+    fn start_auth_with(
+        name: &str,
+        args: Vec<String>,
+        input: Option<String>,
+    ) -> Result<AuthStruct, String> {
+        log_info(format!("Start login for {}", name));
+        let mut process = Command::new("rclone")
+            .args(&args)
+            .stdin(match input {
+                Some(_) => Stdio::piped(),
+                None => Stdio::null(),
+            })
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+        if let (Some(input), Some(mut stdin)) = (input, process.stdin.take()) {
+            thread::spawn(move || {
+                // rclone may exit before reading every answer
+                let _ = stdin.write_all(input.as_bytes());
+            });
+        }
+        let (tx, rx) = mpsc::channel::<String>();
+        forward_lines(process.stdout.take(), tx.clone());
+        forward_lines(process.stderr.take(), tx);
+        Ok(AuthStruct {
+            name: name.to_owned(),
+            status: AuthStatus::Starting,
+            url: String::new(),
+            lines: vec![],
+            process,
+            rx,
+        })
+    }
+
+    /**
+     * Runs `rclone config create`, OAuth backends print a link to open
+     */
+    pub fn create_remote(
+        name: &str,
+        backend: &str,
+        params: Vec<String>,
+    ) -> Result<AuthStruct, String> {
+        let mut args = vec![
+            String::from("config"),
+            String::from("create"),
+            name.trim().to_owned(),
+            backend.to_owned(),
+        ];
+        args.extend(params);
+        start_auth(name.trim(), args)
+    }
+
+    pub fn login_google_drive(name: &str, mut params: Vec<String>) -> Result<AuthStruct, String> {
+        params.extend([String::from("config_is_local"), String::from("true")]);
+        create_remote(name, "drive", params)
+    }
+
+    /**
+     * Options the machine with the browser needs to log in the same way
+     */
+    const AUTHORIZE_KEYS: [&str; 5] = [
+        "client_id",
+        "client_secret",
+        "scope",
+        "auth_url",
+        "token_url",
+    ];
+
+    /**
+     * Command to run on a machine with a browser for the two-machine flow, the
+     * options travel as base64 JSON like in rclones own headless prompt
+     */
+    pub fn authorize_command(backend: &str, params: &[String]) -> String {
+        let mut options = Map::new();
+        for pair in params.chunks(2) {
+            if let [key, value] = pair {
+                if AUTHORIZE_KEYS.contains(&key.as_str()) && !value.is_empty() {
+                    options.insert(key.clone(), Value::from(value.as_str()));
+                }
+            }
+        }
+        if options.is_empty() {
+            return format!("rclone authorize \"{}\"", backend);
+        }
+        let json = Value::Object(options).to_string();
+        format!(
+            "rclone authorize \"{}\" \"{}\"",
+            backend,
+            base64(json.as_bytes()).trim_end_matches('=')
+        )
+    }
+
+    /**
+     * Pulls the token out of pasted `rclone authorize` output and checks it,
+     * Ok with the token as compact JSON
+     */
+    pub fn validate_token(pasted: &str) -> Result<String, String> {
+        let start = pasted
+            .find('{')
+            .ok_or("no JSON object in the pasted text")?;
+        let end = pasted.rfind('}').ok_or("the JSON object is not complete")?;
+        if end < start {
+            return Err(String::from("the JSON object is not complete"));
+        }
+        let json: Value = serde_json::from_str(&pasted[start..=end])
+            .map_err(|e| format!("invalid JSON: {}", e))?;
+        match json["access_token"].as_str() {
+            Some(token) if !token.is_empty() => {}
+            _ => return Err(String::from("the token has no access_token")),
+        }
+        if let Some(expiry) = json["expiry"].as_str() {
+            DateTime::parse_from_rfc3339(expiry).map_err(|e| format!("invalid expiry: {}", e))?;
+        }
+        Ok(json.to_string())
+    }
+
+    /**
+     * Creates a remote from a token authorized on another machine, the token
+     * answers the `config_token` prompt on stdin so it never shows in `ps`
+     */
+    pub fn create_with_token(
+        name: &str,
+        backend: &str,
+        params: Vec<String>,
+        token: &str,
+    ) -> Result<AuthStruct, String> {
+        let mut args = vec![
+            String::from("config"),
+            String::from("create"),
+            name.trim().to_owned(),
+            backend.to_owned(),
+        ];
+        args.extend(params);
+        args.extend([String::from("config_is_local"), String::from("false")]);
+        // empty lines take the defaults of the questions that follow, like --non-interactive
+        let input = format!("{}\n{}", token, "\n".repeat(16));
+        start_auth_with(name.trim(), args, Some(input))
+    }
+
+    /**
+     * SSH sessions have no browser, default to the two-machine flow there
+     */
+    pub fn is_headless() -> bool {
+        env::var("SSH_CONNECTION").is_ok() || env::var("SSH_TTY").is_ok()
+    }
+
+    fn find_url(line: &str) -> Option<String> {
+        let start = line.find("http://").or(line.find("https://"))?;
+        let url = line[start..].split_whitespace().next()?;
+        Some(url.trim_end_matches(['"', '\'', ')', '.']).to_owned())
+    }
+
+    /**
+     * Links to rclone's own callback server only open on this machine
+     */
+    pub fn is_loopback(url: &str) -> bool {
+        let rest = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+        let host = rest.split('/').next().unwrap_or_default();
+        let host = match host.strip_prefix('[') {
+            Some(v6) => v6.split(']').next().unwrap_or_default(),
+            None => host.split(':').next().unwrap_or_default(),
+        };
+        host == "localhost" || host == "::1" || host.starts_with("127.")
+    }
+
+    impl AuthStruct {
+        /**
+         * Reads new output and process state, returns true once it just finished
+         */
+        pub fn poll(&mut self) -> bool {
+            if self.status.finished() {
+                return false;
+            }
+            while let Ok(line) = self.rx.try_recv() {
+                if self.url.is_empty() {
+                    if let Some(url) = find_url(&line) {
+                        self.url = url;
+                        self.status = AuthStatus::WaitingForBrowser;
+                    }
+                }
+                if line.contains("Waiting for code") {
+                    self.status = AuthStatus::WaitingForBrowser;
+                } else if line.contains("Got code") {
+                    self.status = AuthStatus::GotCode;
+                }
+                log_trace(line.clone());
+                self.lines.push(line);
+            }
+            match self.process.try_wait() {
+                Ok(Some(status)) => {
+                    // last lines may still be in the channel
+                    while let Ok(line) = self.rx.try_recv() {
+                        self.lines.push(line);
+                    }
+                    self.status = match status.success() {
+                        true => AuthStatus::Success,
+                        false => AuthStatus::Failed,
+                    };
+                    match status.success() {
+                        true => log_info(format!("Created remote {}", self.name)),
+                        false => log_error(format!("Creating {} failed ({})", self.name, status)),
+                    }
+                    true
+                }
+                Ok(None) => false,
+                Err(e) => {
+                    log_error(e.to_string());
+                    self.status = AuthStatus::Failed;
+                    true
+                }
+            }
+        }
+
+        pub fn cancel(&mut self) {
+            if self.status.finished() {
+                return;
+            }
+            match self.process.kill() {
+                Ok(_) => log_warning(format!("Login for {} cancelled", self.name)),
+                Err(e) => log_error(e.to_string()),
+            }
+            let _ = self.process.wait();
+            self.status = AuthStatus::Cancelled;
+        }
+    }
+}
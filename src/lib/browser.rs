@@ -0,0 +1,218 @@
+#![allow(dead_code)]
+
+pub mod browser {
+    use std::{
+        cmp::Reverse,
+        io::{BufRead, BufReader},
+        process::{Child, Command, Stdio},
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    use chrono::{DateTime, FixedOffset};
+    use serde_json::Value;
+
+    use crate::lib::utils::utils::*;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct FileStruct {
+        pub name: String,
+        pub path: String,
+        pub size: i64,
+        pub mod_time: Option<DateTime<FixedOffset>>,
+        pub mime: String,
+        pub is_dir: bool,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum SortKey {
+        Name,
+        Size,
+        Modified,
+    }
+
+    impl SortKey {
+        pub fn next(&self) -> SortKey {
+            match self {
+                SortKey::Name => SortKey::Size,
+                SortKey::Size => SortKey::Modified,
+                SortKey::Modified => SortKey::Name,
+            }
+        }
+
+        pub fn label(&self) -> &'static str {
+            match self {
+                SortKey::Name => "name",
+                SortKey::Size => "size",
+                SortKey::Modified => "modified",
+            }
+        }
+    }
+
+    /**
+     * A running `rclone lsjson`, entries arrive one by one
+     */
+    pub struct ListingStruct {
+        pub remote: String,
+        pub path: String,
+        pub done: bool,
+        pub failed: bool,
+        process: Child,
+        rx: Receiver<FileStruct>,
+    }
+
+    /**
+     * lsjson prints one object per line between `[` and `]`
+     */
+    fn parse_line(line: &str) -> Option<FileStruct> {
+        let line = line.trim().trim_end_matches(',');
+        if !line.starts_with('{') {
+            return None;
+        }
+        let json: Value = serde_json::from_str(line).ok()?;
+        Some(FileStruct {
+            name: json["Name"].as_str()?.to_owned(),
+            path: json["Path"].as_str().unwrap_or_default().to_owned(),
+            size: json["Size"].as_i64().unwrap_or(-1),
+            mod_time: json["ModTime"]
+                .as_str()
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok()),
+            mime: json["MimeType"].as_str().unwrap_or_default().to_owned(),
+            is_dir: json["IsDir"].as_bool().unwrap_or(false),
+        })
+    }
+
+    pub fn remote_path(remote: &str, path: &str) -> String {
+        format!("{}:{}", remote, path)
+    }
+
+    /**
+     * Lists one folder in the background, nothing recursive
+     */
+    pub fn start_listing(remote: &str, path: &str) -> Result<ListingStruct, String> {
+        start_listing_with(remote, path, &[])
+    }
+
+    /**
+     * Same with more lsjson flags, filters or `-R`, an empty remote lists a local folder
+     */
+    pub fn start_listing_with(
+        remote: &str,
+        path: &str,
+        extra: &[String],
+    ) -> Result<ListingStruct, String> {
+        let target = match remote.is_empty() {
+            true => path.to_owned(),
+            false => remote_path(remote, path),
+        };
+        log_trace(format!("rclone lsjson {} {}", target, extra.join(" ")));
+        let mut process = Command::new("rclone")
+            .args(["lsjson", &target])
+            .args(extra)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+        let (tx, rx) = mpsc::channel::<FileStruct>();
+        if let Some(stdout) = process.stdout.take() {
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if let Some(file) = parse_line(&line) {
+                        if tx.send(file).is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+        if let Some(stderr) = process.stderr.take() {
+            thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    log_error(line);
+                }
+            });
+        }
+        Ok(ListingStruct {
+            remote: remote.to_owned(),
+            path: path.to_owned(),
+            done: false,
+            failed: false,
+            process,
+            rx,
+        })
+    }
+
+    impl ListingStruct {
+        /**
+         * Entries received since the last call, marks the listing done once rclone exits
+         */
+        pub fn poll(&mut self) -> Vec<FileStruct> {
+            let mut files: Vec<FileStruct> = self.rx.try_iter().collect();
+            if self.done {
+                return files;
+            }
+            match self.process.try_wait() {
+                Ok(Some(status)) => {
+                    // the reader thread may still hold the last entries
+                    files.extend(self.rx.iter());
+                    self.done = true;
+                    self.failed = !status.success();
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    log_error(e.to_string());
+                    self.done = true;
+                    self.failed = true;
+                }
+            }
+            files
+        }
+
+        pub fn cancel(&mut self) {
+            if !self.done {
+                let _ = self.process.kill();
+                let _ = self.process.wait();
+                self.done = true;
+            }
+        }
+    }
+
+    /**
+     * Folders first, then by the key
+     */
+    pub fn sort_files(files: &mut [FileStruct], key: SortKey, reverse: bool) {
+        match key {
+            SortKey::Name => files.sort_by_key(|f| f.name.to_lowercase()),
+            SortKey::Size => files.sort_by_key(|f| Reverse(f.size)),
+            SortKey::Modified => files.sort_by_key(|f| Reverse(f.mod_time)),
+        }
+        if reverse {
+            files.reverse();
+        }
+        files.sort_by_key(|f| !f.is_dir);
+    }
+
+    pub fn parent_path(path: &str) -> String {
+        match path.trim_end_matches('/').rsplit_once('/') {
+            Some((parent, _)) => parent.to_owned(),
+            None => String::new(),
+        }
+    }
+
+    pub fn child_path(path: &str, name: &str) -> String {
+        match path.is_empty() {
+            true => name.to_owned(),
+            false => format!("{}/{}", path.trim_end_matches('/'), name),
+        }
+    }
+
+    /**
+     * `remote: › folder › sub`
+     */
+    pub fn breadcrumb(remote: &str, path: &str) -> String {
+        let mut crumbs = vec![format!("{}:", remote)];
+        crumbs.extend(path.split('/').filter(|p| !p.is_empty()).map(String::from));
+        crumbs.join(" › ")
+    }
+}
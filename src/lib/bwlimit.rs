@@ -0,0 +1,131 @@
+#![allow(dead_code)]
+
+pub mod bwlimit {
+    use crate::lib::{form::form::*, mount::mount::RcStruct, utils::utils::run_command_with};
+
+    const DAYS: [&str; 7] = ["Mon", "Tue", "Wed", "Thu", "Fri", "Sat", "Sun"];
+
+    /**
+     * `10M`, `1.5G`, `512k` or `off`
+     */
+    fn valid_size(size: &str) -> bool {
+        if size == "off" {
+            return true;
+        }
+        let number = size.trim_end_matches(|c: char| "bBkKmMgGtTpP".contains(c));
+        size.len() - number.len() <= 1 && !number.is_empty() && number.parse::<f64>().is_ok()
+    }
+
+    /**
+     * A rate is one size or `upload:download`
+     */
+    fn valid_rate(rate: &str) -> bool {
+        match rate.split_once(':') {
+            Some((up, down)) => valid_size(up) && valid_size(down),
+            None => valid_size(rate),
+        }
+    }
+
+    fn valid_time(time: &str) -> bool {
+        let time = match time.split_once('-') {
+            Some((day, time)) if DAYS.contains(&day) => time,
+            Some(_) => return false,
+            None => time,
+        };
+        match time.split_once(':') {
+            Some((hours, minutes)) => {
+                matches!(hours.parse::<u8>(), Ok(h) if h < 24 && hours.len() == 2)
+                    && matches!(minutes.parse::<u8>(), Ok(m) if m < 60 && minutes.len() == 2)
+            }
+            None => false,
+        }
+    }
+
+    /**
+     * Checks rclone `--bwlimit` syntax, a rate or a timetable
+     * like `08:00,512k Mon-18:00,10M:off 23:00,off`
+     */
+    pub fn validate_bwlimit(limit: &str) -> Result<(), String> {
+        let entries: Vec<&str> = limit.split_whitespace().collect();
+        if entries.is_empty() {
+            return Ok(());
+        }
+        if entries.len() == 1 && !entries[0].contains(',') {
+            return match valid_rate(entries[0]) {
+                true => Ok(()),
+                false => Err(format!("'{}' is not a rate like 10M or 10M:1M", entries[0])),
+            };
+        }
+        for entry in entries {
+            match entry.split_once(',') {
+                Some((time, rate)) if valid_time(time) && valid_rate(rate) => {}
+                _ => {
+                    return Err(format!(
+                        "'{}' is not a timetable entry like 08:00,512k",
+                        entry
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn is_timetable(limit: &str) -> bool {
+        limit.contains(',')
+    }
+
+    /**
+     * Changes the limit of a running mount through its remote control
+     */
+    pub fn apply_bwlimit(rc: &RcStruct, limit: &str) -> Result<(), String> {
+        if is_timetable(limit) {
+            return Err(String::from("timetables only apply when mounting"));
+        }
+        let rate = match limit.trim() {
+            "" => "off",
+            rate => rate,
+        };
+        let url = rc
+            .url()
+            .ok_or_else(|| String::from("the remote control isnt up yet"))?;
+        run_command_with(
+            "rclone",
+            &[
+                String::from("rc"),
+                String::from("--url"),
+                url,
+                String::from("core/bwlimit"),
+                format!("rate={}", rate),
+            ],
+            &rc.client_envs(),
+        )
+        .map(|_| ())
+    }
+
+    pub fn bwlimit_form(remote: &str, global: &str) -> FormStruct {
+        FormStruct::with_fields(vec![
+            FieldStruct::new("remote", "This remote", remote, FieldKind::Text),
+            FieldStruct::new("global", "Every remote", global, FieldKind::Text),
+        ])
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn accepts_rates_and_timetables() {
+            for limit in ["", "10M", "512k", "10M:1M", "off", "08:00,512k 23:00,off"] {
+                assert_eq!(validate_bwlimit(limit), Ok(()), "{}", limit);
+            }
+            assert_eq!(validate_bwlimit("Mon-18:00,10M:off"), Ok(()));
+        }
+
+        #[test]
+        fn rejects_bad_limits() {
+            for limit in ["fast", "10X", "25:00,1M", "08:00,fast", "08:00,1M 09:00"] {
+                assert!(validate_bwlimit(limit).is_err(), "{}", limit);
+            }
+        }
+    }
+}
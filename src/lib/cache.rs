@@ -0,0 +1,144 @@
+#![allow(dead_code)]
+
+pub mod cache {
+    use std::{
+        env, fs, io,
+        path::{Path, PathBuf},
+        sync::mpsc::{self, Receiver},
+        thread,
+        time::SystemTime,
+    };
+
+    use crate::lib::utils::utils::run_command;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct CacheStruct {
+        pub name: String,
+        pub path: PathBuf,
+        pub meta_path: PathBuf,
+        pub size: u64,
+        pub files: u64,
+        pub modified: Option<SystemTime>,
+    }
+
+    /**
+     * Default rclone cache directory, VFS caches live in `vfs/<remote>`
+     */
+    pub fn get_cache_dir() -> PathBuf {
+        match env::consts::OS {
+            "windows" => {
+                let path = env::var("LOCALAPPDATA").expect("Can not get LocalAppData folder");
+                Path::new(&path).join("rclone")
+            }
+            "macos" => {
+                let path = env::var("HOME").expect("Can not get HOME directory");
+                Path::new(&path).join("Library/Caches/rclone")
+            }
+            _ => match env::var("XDG_CACHE_HOME") {
+                Ok(path) if !path.is_empty() => Path::new(&path).join("rclone"),
+                _ => {
+                    let path = env::var("HOME").expect("Can not get HOME directory");
+                    Path::new(&path).join(".cache/rclone")
+                }
+            },
+        }
+    }
+
+    /**
+     * Adds up size and count of files under a directory, and the newest change
+     */
+    fn walk(path: &Path, cache: &mut CacheStruct) {
+        let entries = match fs::read_dir(path) {
+            Ok(entries) => entries,
+            Err(_) => return,
+        };
+        for entry in entries.flatten() {
+            let metadata = match entry.metadata() {
+                Ok(metadata) => metadata,
+                Err(_) => continue,
+            };
+            if metadata.is_dir() {
+                walk(&entry.path(), cache);
+            } else {
+                cache.size += metadata.len();
+                cache.files += 1;
+                if let Ok(modified) = metadata.modified() {
+                    cache.modified = cache.modified.max(Some(modified));
+                }
+            }
+        }
+    }
+
+    pub fn scan_caches() -> Vec<CacheStruct> {
+        let dir = get_cache_dir();
+        let mut caches: Vec<CacheStruct> = match fs::read_dir(dir.join("vfs")) {
+            Ok(entries) => entries
+                .flatten()
+                .filter(|e| e.path().is_dir())
+                .map(|e| {
+                    let name = e.file_name().to_string_lossy().to_string();
+                    let mut cache = CacheStruct {
+                        path: e.path(),
+                        meta_path: dir.join("vfsMeta").join(&name),
+                        name,
+                        size: 0,
+                        files: 0,
+                        modified: None,
+                    };
+                    walk(&e.path(), &mut cache);
+                    cache
+                })
+                .collect(),
+            Err(_) => vec![],
+        };
+        caches.sort_by_key(|c| std::cmp::Reverse(c.size));
+        caches
+    }
+
+    /**
+     * Caches and disk usage found by a scan
+     */
+    pub type CacheScan = (Vec<CacheStruct>, Option<(u64, u64)>);
+
+    /**
+     * Walks the caches in the background, big caches take a while
+     */
+    pub fn start_cache_scan() -> Receiver<CacheScan> {
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let _ = tx.send((scan_caches(), disk_usage(&get_cache_dir())));
+        });
+        rx
+    }
+
+    pub fn purge_cache(cache: &CacheStruct) -> io::Result<()> {
+        fs::remove_dir_all(&cache.path)?;
+        if cache.meta_path.exists() {
+            fs::remove_dir_all(&cache.meta_path)?;
+        }
+        Ok(())
+    }
+
+    /**
+     * Total and available bytes of the disk holding a path, from `df`
+     */
+    pub fn disk_usage(path: &Path) -> Option<(u64, u64)> {
+        if env::consts::OS == "windows" {
+            return None;
+        }
+        let mut path = path.to_path_buf();
+        while !path.exists() {
+            path = path.parent()?.to_path_buf();
+        }
+        let output = run_command(
+            "df",
+            &[String::from("-Pk"), path.to_string_lossy().to_string()],
+        )
+        .ok()?;
+        let line = output.lines().nth(1)?;
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        let total = columns.get(1)?.parse::<u64>().ok()?;
+        let available = columns.get(3)?.parse::<u64>().ok()?;
+        Some((total * 1024, available * 1024))
+    }
+}
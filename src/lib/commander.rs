@@ -0,0 +1,224 @@
+#![allow(dead_code)]
+
+pub mod commander {
+    use std::{fs, path::Path};
+
+    use chrono::{DateTime, FixedOffset, Utc};
+
+    use crate::lib::{
+        app::app_mod::StatefulList,
+        browser::browser::{
+            breadcrumb, child_path, parent_path, sort_files, start_listing, FileStruct,
+            ListingStruct, SortKey,
+        },
+        utils::utils::log_error,
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum CommanderOp {
+        Copy,
+        Move,
+        Delete,
+        Mkdir,
+        Rename,
+    }
+
+    impl CommanderOp {
+        pub fn label(&self) -> &'static str {
+            match self {
+                CommanderOp::Copy => "copy",
+                CommanderOp::Move => "move",
+                CommanderOp::Delete => "delete",
+                CommanderOp::Mkdir => "mkdir",
+                CommanderOp::Rename => "rename",
+            }
+        }
+    }
+
+    /**
+     * One side of the commander, a local folder or a folder of a remote
+     */
+    pub struct PaneStruct {
+        pub remote: Option<String>,
+        pub path: String,
+        pub files: StatefulList<FileStruct>,
+        pub listing: Option<ListingStruct>,
+    }
+
+    fn local_files(dir: &str) -> Result<Vec<FileStruct>, String> {
+        let utc = FixedOffset::east_opt(0).unwrap();
+        Ok(fs::read_dir(dir)
+            .map_err(|e| format!("{}: {}", dir, e))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| {
+                let metadata = entry.metadata().ok();
+                FileStruct {
+                    name: entry.file_name().to_string_lossy().to_string(),
+                    path: entry.path().to_string_lossy().to_string(),
+                    size: metadata.as_ref().map_or(-1, |m| m.len() as i64),
+                    mod_time: metadata
+                        .as_ref()
+                        .and_then(|m| m.modified().ok())
+                        .map(|t| DateTime::<Utc>::from(t).with_timezone(&utc)),
+                    mime: String::new(),
+                    is_dir: entry.path().is_dir(),
+                }
+            })
+            .collect())
+    }
+
+    impl PaneStruct {
+        pub fn new(remote: Option<String>, path: &str) -> PaneStruct {
+            let mut pane = PaneStruct {
+                remote,
+                path: path.to_owned(),
+                files: StatefulList::with_items(&[]),
+                listing: None,
+            };
+            pane.load();
+            pane
+        }
+
+        pub fn title(&self) -> String {
+            match &self.remote {
+                Some(remote) => breadcrumb(remote, &self.path),
+                None => self.path.clone(),
+            }
+        }
+
+        /**
+         * What rclone gets for `name` in this pane, the folder itself when empty
+         */
+        pub fn target(&self, name: &str) -> String {
+            match &self.remote {
+                Some(remote) => format!("{}:{}", remote, child_path(&self.path, name)),
+                None => match name.is_empty() {
+                    true => self.path.clone(),
+                    false => Path::new(&self.path)
+                        .join(name)
+                        .to_string_lossy()
+                        .to_string(),
+                },
+            }
+        }
+
+        pub fn selected(&self) -> Option<&FileStruct> {
+            self.files
+                .state
+                .selected()
+                .and_then(|i| self.files.items.get(i))
+        }
+
+        pub fn loading(&self) -> bool {
+            self.listing.as_ref().is_some_and(|l| !l.done)
+        }
+
+        /**
+         * Reads the folder again, remotes load in the background
+         */
+        pub fn load(&mut self) {
+            if let Some(listing) = self.listing.as_mut() {
+                listing.cancel();
+            }
+            self.listing = None;
+            self.files = StatefulList::with_items(&[]);
+            match &self.remote {
+                Some(remote) => match start_listing(remote, &self.path) {
+                    Ok(listing) => self.listing = Some(listing),
+                    Err(e) => log_error(e),
+                },
+                None => match local_files(&self.path) {
+                    Ok(mut files) => {
+                        sort_files(&mut files, SortKey::Name, false);
+                        self.files = StatefulList::with_items(&files);
+                    }
+                    Err(e) => log_error(e),
+                },
+            }
+            self.files.state.select(Some(0));
+        }
+
+        /**
+         * Takes entries a remote listing read since the last call
+         */
+        pub fn poll(&mut self) {
+            let files = match self.listing.as_mut() {
+                Some(listing) if !listing.done => listing.poll(),
+                _ => return,
+            };
+            if files.is_empty() {
+                return;
+            }
+            let selected = self.selected().map(|f| f.name.clone());
+            self.files.items.extend(files);
+            sort_files(&mut self.files.items, SortKey::Name, false);
+            let i = selected
+                .and_then(|name| self.files.items.iter().position(|f| f.name == name))
+                .unwrap_or(0);
+            self.files.state.select(Some(i));
+        }
+
+        pub fn enter(&mut self) {
+            let dir = match self.selected() {
+                Some(file) if file.is_dir => file.name.clone(),
+                _ => return,
+            };
+            self.path = match &self.remote {
+                Some(_) => child_path(&self.path, &dir),
+                None => self.target(&dir),
+            };
+            self.load();
+        }
+
+        pub fn up(&mut self) {
+            let parent = match &self.remote {
+                Some(_) if self.path.is_empty() => return,
+                Some(_) => parent_path(&self.path),
+                None => match Path::new(&self.path).parent() {
+                    Some(parent) => parent.to_string_lossy().to_string(),
+                    None => return,
+                },
+            };
+            self.path = parent;
+            self.load();
+        }
+
+        /**
+         * Points the pane at the root of another remote, or the local home for None
+         */
+        pub fn switch(&mut self, remote: Option<String>, home: &str) {
+            self.path = match remote {
+                Some(_) => String::new(),
+                None => home.to_owned(),
+            };
+            self.remote = remote;
+            self.load();
+        }
+    }
+
+    /**
+     * rclone arguments of an operation on `file` of `from`, `name` is the
+     * new folder or file name for mkdir and rename
+     */
+    pub fn op_args(
+        op: CommanderOp,
+        from: &PaneStruct,
+        to: &PaneStruct,
+        file: Option<&FileStruct>,
+        name: &str,
+    ) -> Option<Vec<String>> {
+        if op == CommanderOp::Mkdir {
+            return Some(vec![String::from("mkdir"), from.target(name)]);
+        }
+        let file = file?;
+        let source = from.target(&file.name);
+        let args = match op {
+            CommanderOp::Copy => vec![String::from("copyto"), source, to.target(&file.name)],
+            CommanderOp::Move => vec![String::from("moveto"), source, to.target(&file.name)],
+            CommanderOp::Rename => vec![String::from("moveto"), source, from.target(name)],
+            CommanderOp::Delete if file.is_dir => vec![String::from("purge"), source],
+            _ => vec![String::from("deletefile"), source],
+        };
+        Some(args)
+    }
+}
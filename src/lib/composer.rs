@@ -0,0 +1,186 @@
+#![allow(dead_code)]
+
+pub mod composer {
+    use crate::lib::{
+        config::config::{ConfigStruct, DriveStruct},
+        form::form::*,
+        providers::providers::NAME_KEY,
+        utils::utils::run_rclone,
+    };
+
+    pub const KINDS: [&str; 3] = ["union", "combine", "alias"];
+
+    pub const POLICIES: [&str; 15] = [
+        "epall", "epff", "eplfs", "eplno", "eplus", "epmfs", "eprand", "all", "ff", "lfs", "lno",
+        "lus", "mfs", "newest", "rand",
+    ];
+
+    const UPSTREAM_PREFIX: &str = ":upstream:";
+
+    fn policy_field(key: &str, value: &str, help: &str) -> FieldStruct {
+        FieldStruct::new(
+            key,
+            key,
+            value,
+            FieldKind::Choice(POLICIES.iter().map(|p| p.to_string()).collect()),
+        )
+        .with_help(help)
+    }
+
+    /**
+     * One path field per remote, empty ones are left out,
+     * values are kept from the previous form when the kind changes
+     */
+    pub fn composer_form(
+        conf: &ConfigStruct,
+        selected: &DriveStruct,
+        previous: Option<&FormStruct>,
+    ) -> FormStruct {
+        let previous_value = |key: &str, default: &str| match previous {
+            Some(form) if form.fields.iter().any(|f| f.key == key) => form.value(key),
+            _ => default.to_owned(),
+        };
+        let kind = previous_value("type", KINDS[0]);
+
+        let mut fields = vec![
+            FieldStruct::new(
+                NAME_KEY,
+                "Remote name",
+                &previous_value(NAME_KEY, &conf.suggest_name(&kind)),
+                FieldKind::Text,
+            )
+            .with_help("Name of the composed remote"),
+            FieldStruct::new(
+                "type",
+                "type",
+                &kind,
+                FieldKind::Choice(KINDS.iter().map(|k| k.to_string()).collect()),
+            )
+            .with_help(match kind.as_str() {
+                "union" => "Merges upstreams into one tree",
+                "combine" => "Puts each upstream in its own top folder",
+                _ => "Points at a single path of one remote",
+            }),
+        ];
+        if kind == "union" {
+            fields.push(policy_field(
+                "action_policy",
+                &previous_value("action_policy", "epall"),
+                "Where to delete, rename and set times",
+            ));
+            fields.push(policy_field(
+                "create_policy",
+                &previous_value("create_policy", "epmfs"),
+                "Where new files are written",
+            ));
+            fields.push(policy_field(
+                "search_policy",
+                &previous_value("search_policy", "ff"),
+                "Which upstream answers reads",
+            ));
+        }
+        for drive in conf.drives.iter() {
+            let key = format!("{}{}", UPSTREAM_PREFIX, drive.name);
+            let default = match drive.name == selected.name {
+                true => "/",
+                false => "",
+            };
+            let help = match kind.as_str() {
+                "union" => "Path to include, '/' for the root, ':ro' or ':nc' suffix allowed",
+                _ => "Path to include, '/' for the root, empty to skip",
+            };
+            fields.push(
+                FieldStruct::new(
+                    &key,
+                    &format!("{}:", drive.name),
+                    &previous_value(&key, default),
+                    FieldKind::Text,
+                )
+                .with_help(help),
+            );
+        }
+
+        let mut form = FormStruct::with_fields(fields);
+        if let Some(previous) = previous {
+            form.selected = previous.selected.min(form.fields.len() - 1);
+        }
+        form
+    }
+
+    /**
+     * `remote:path` of every upstream with a path
+     */
+    fn picked_upstreams(form: &FormStruct) -> Vec<(String, String)> {
+        form.fields
+            .iter()
+            .filter_map(|f| {
+                let name = f.key.strip_prefix(UPSTREAM_PREFIX)?;
+                let path = f.value.trim();
+                match path.is_empty() {
+                    true => None,
+                    false => Some((
+                        name.to_owned(),
+                        format!("{}:{}", name, path.trim_start_matches('/')),
+                    )),
+                }
+            })
+            .collect()
+    }
+
+    /**
+     * `key value` pairs for `rclone config create`
+     */
+    pub fn composer_params(form: &FormStruct) -> Result<Vec<String>, String> {
+        let kind = form.value("type");
+        let upstreams = picked_upstreams(form);
+        if upstreams.is_empty() {
+            return Err(String::from("no upstream picked"));
+        }
+        if let Some((_, remote)) = upstreams.iter().find(|(_, r)| r.contains(' ')) {
+            return Err(format!("{} contains a space", remote));
+        }
+        let params = match kind.as_str() {
+            "union" => vec![
+                String::from("upstreams"),
+                upstreams
+                    .iter()
+                    .map(|(_, remote)| remote.clone())
+                    .collect::<Vec<String>>()
+                    .join(" "),
+                String::from("action_policy"),
+                form.value("action_policy"),
+                String::from("create_policy"),
+                form.value("create_policy"),
+                String::from("search_policy"),
+                form.value("search_policy"),
+            ],
+            "combine" => vec![
+                String::from("upstreams"),
+                upstreams
+                    .iter()
+                    .map(|(name, remote)| format!("{}={}", name, remote))
+                    .collect::<Vec<String>>()
+                    .join(" "),
+            ],
+            _ => match upstreams.len() {
+                1 => vec![String::from("remote"), upstreams[0].1.clone()],
+                _ => return Err(String::from("an alias points at exactly one upstream")),
+            },
+        };
+        Ok(params)
+    }
+
+    pub fn create_composed(conf: &ConfigStruct, form: &FormStruct) -> Result<String, String> {
+        let name = form.value(NAME_KEY).trim().to_owned();
+        conf.validate_name(&name)?;
+        let mut args = vec![
+            String::from("config"),
+            String::from("create"),
+            name.clone(),
+            form.value("type"),
+        ];
+        args.extend(composer_params(form)?);
+        args.push(String::from("--non-interactive"));
+        run_rclone(&args).map(|_| name)
+    }
+}
@@ -2,11 +2,13 @@
 
 pub mod config {
     use std::env;
-    use std::fs::File;
+    use std::fs::{self, File};
     use std::io::{BufRead, BufReader};
 
-    use chrono::{DateTime, FixedOffset};
-    use serde_json::Value;
+    use chrono::{DateTime, FixedOffset, Utc};
+    use serde_json::{json, Value};
+
+    use crate::lib::{filter::filter::split_target, utils::utils::log_error};
 
     #[derive(Debug, Clone)]
     pub struct ConfigStruct {
@@ -19,7 +21,8 @@ pub mod config {
         pub name: String,
         pub drive_type: String,
         pub scope: String,
-        pub token: TokenStruct,
+        pub token: Option<TokenStruct>,
+        pub options: Vec<(String, String)>,
     }
 
     #[derive(Debug, Clone, PartialEq)]
@@ -30,66 +33,610 @@ pub mod config {
         pub expiry: DateTime<FixedOffset>,
     }
 
-    enum ConfigType {
+    /**
+     * Backends that log in through OAuth and keep a token
+     */
+    const OAUTH_BACKENDS: [&str; 12] = [
+        "drive",
+        "onedrive",
+        "dropbox",
+        "box",
+        "pcloud",
+        "google photos",
+        "gphotos",
+        "yandex",
+        "hidrive",
+        "zoho",
+        "jottacloud",
+        "putio",
+    ];
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum TokenHealth {
+        Valid,
+        Refreshable,
+        Expired,
+        Missing,
+        NotNeeded,
+    }
+
+    impl DriveStruct {
+        /**
+         * Access tokens expire hourly, rclone renews them while a refresh token exists
+         */
+        pub fn token_health(&self) -> TokenHealth {
+            match &self.token {
+                Some(token) if token.expiry > Utc::now() => TokenHealth::Valid,
+                Some(token) if !token.refresh_token.is_empty() => TokenHealth::Refreshable,
+                Some(_) => TokenHealth::Expired,
+                None if self.is_service_account() => TokenHealth::NotNeeded,
+                None if OAUTH_BACKENDS.contains(&self.drive_type.as_str()) => TokenHealth::Missing,
+                None => TokenHealth::NotNeeded,
+            }
+        }
+
+        pub fn option(&self, key: &str) -> Option<&str> {
+            self.options
+                .iter()
+                .find(|(k, _)| k == key)
+                .map(|(_, v)| v.as_str())
+        }
+
+        /**
+         * Drive remotes authenticated by a service account have no token
+         */
+        pub fn is_service_account(&self) -> bool {
+            self.option("service_account_file").is_some()
+                || self.option("service_account_credentials").is_some()
+        }
+
+        /**
+         * Upstreams of a union or combine remote, as written in the config
+         */
+        pub fn upstreams(&self) -> Vec<String> {
+            match self.option("upstreams") {
+                Some(upstreams) => upstreams.split_whitespace().map(String::from).collect(),
+                None => vec![],
+            }
+        }
+    }
+
+    /**
+     * Keys whose values should never be shown in clear
+     */
+    pub fn is_secret(key: &str) -> bool {
+        ["pass", "secret", "token", "key", "credentials", "sas_url"]
+            .iter()
+            .any(|s| key.contains(s))
+            && !key.ends_with("_id")
+    }
+
+    /**
+     * Chain of wrapped remotes like `crypt -> gdrive:enc (drive)`, None for plain ones
+     */
+    pub fn layering(drives: &[DriveStruct], drive: &DriveStruct) -> Option<String> {
+        let mut chain = vec![drive.drive_type.clone()];
+        let mut current = drive;
+        // bounded in case remotes wrap each other in a loop
+        for _ in 0..8 {
+            let remote = match current.option("remote") {
+                Some(remote) => remote,
+                None => break,
+            };
+            let name = remote.split_once(':').map_or(remote, |(name, _)| name);
+            match drives.iter().find(|d| d.name == name) {
+                Some(next) => {
+                    chain.push(format!("{} ({})", remote, next.drive_type));
+                    current = next;
+                }
+                None => {
+                    chain.push(remote.to_owned());
+                    break;
+                }
+            }
+        }
+        match chain.len() {
+            1 => None,
+            _ => Some(chain.join(" -> ")),
+        }
+    }
+
+    impl ConfigStruct {
+        /**
+         * Remotes built on top of another one, like crypt, alias or union
+         */
+        pub fn dependents(&self, name: &str) -> Vec<String> {
+            let prefix = format!("{}:", name);
+            self.drives
+                .iter()
+                .filter(|d| d.name != name)
+                .filter(|d| {
+                    d.options.iter().any(|(key, value)| {
+                        key != "type"
+                            && value.split_whitespace().any(|word| {
+                                // combine upstreams look like dir=remote:path
+                                let word = word.split_once('=').map_or(word, |(_, w)| w);
+                                word.starts_with(&prefix)
+                            })
+                    })
+                })
+                .map(|d| d.name.clone())
+                .collect()
+        }
+
+        /**
+         * Renames a remote and every reference to it in other remotes
+         */
+        pub fn rename(&mut self, name: &str, new_name: &str) {
+            let prefix = format!("{}:", name);
+            for drive in self.drives.iter_mut() {
+                if drive.name == name {
+                    drive.name = new_name.to_owned();
+                    continue;
+                }
+                for (key, value) in drive.options.iter_mut() {
+                    if key == "type" {
+                        continue;
+                    }
+                    *value = value
+                        .split(' ')
+                        .map(|word| {
+                            let (head, tail) = match word.split_once('=') {
+                                Some((head, tail)) => (format!("{}=", head), tail),
+                                None => (String::new(), word),
+                            };
+                            match tail.strip_prefix(&prefix) {
+                                Some(path) => format!("{}{}:{}", head, new_name, path),
+                                None => word.to_owned(),
+                            }
+                        })
+                        .collect::<Vec<String>>()
+                        .join(" ");
+                }
+            }
+        }
+
+        /**
+         * rclone remote names: letters, numbers, `_ - . + @` and spaces,
+         * not starting with `-` or a space, not ending with a space
+         */
+        pub fn validate_name(&self, name: &str) -> Result<(), String> {
+            if name.is_empty() {
+                return Err(String::from("name is empty"));
+            }
+            if let Some(c) = name
+                .chars()
+                .find(|c| !(c.is_alphanumeric() || "_-.+@ ".contains(*c)))
+            {
+                return Err(format!("'{}' is not allowed", c));
+            }
+            if name.starts_with('-') || name.starts_with(' ') {
+                return Err(String::from("cant start with '-' or a space"));
+            }
+            if name.ends_with(' ') {
+                return Err(String::from("cant end with a space"));
+            }
+            if self.drives.iter().any(|d| d.name == name) {
+                return Err(format!("already exists, try {}", self.suggest_name(name)));
+            }
+            Ok(())
+        }
+
+        /**
+         * First free `name-2`, `name-3` ...
+         */
+        pub fn suggest_name(&self, name: &str) -> String {
+            let taken = |candidate: &str| self.drives.iter().any(|d| d.name == candidate);
+            if !taken(name) {
+                return name.to_owned();
+            }
+            let mut index = 2;
+            while taken(&format!("{}-{}", name, index)) {
+                index += 1;
+            }
+            format!("{}-{}", name, index)
+        }
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct SettingsStruct {
+        pub path: String,
+        pub safe_mode: bool,
+        pub bwlimit: String,
+        pub cache_warn_size: String,
+        pub headless_auth: bool,
+        pub max_jobs: usize,
+        pub quota_warn: u8,
+        pub mounts: Vec<MountOptionsStruct>,
+        pub serves: Vec<ServeOptionsStruct>,
+        pub syncs: Vec<SyncJobStruct>,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct MountOptionsStruct {
+        pub name: String,
+        pub mount_point: String,
+        pub vfs_cache_mode: String,
+        pub allow_other: bool,
+        pub read_only: bool,
+        pub bwlimit: String,
+        pub extra_flags: Vec<String>,
+        pub filter: FilterSetStruct,
+    }
+
+    /**
+     * Include and exclude rules plus size and age limits, the same set works
+     * for a mount, a sync job or a listing
+     */
+    #[derive(Debug, Clone, PartialEq, Default)]
+    pub struct FilterSetStruct {
+        pub rules: Vec<String>,
+        pub filter_from: String,
+        pub min_size: String,
+        pub max_size: String,
+        pub min_age: String,
+        pub max_age: String,
+    }
+
+    impl FilterSetStruct {
+        pub fn is_empty(&self) -> bool {
+            *self == FilterSetStruct::default()
+        }
+
+        pub fn to_json(&self) -> Value {
+            json!({
+                "rules": self.rules,
+                "filter_from": self.filter_from,
+                "min_size": self.min_size,
+                "max_size": self.max_size,
+                "min_age": self.min_age,
+                "max_age": self.max_age,
+            })
+        }
+
+        pub fn from_json(json: &Value) -> FilterSetStruct {
+            let text = |key: &str| json[key].as_str().unwrap_or_default().to_owned();
+            FilterSetStruct {
+                rules: match json["rules"].as_array() {
+                    Some(rules) => rules
+                        .iter()
+                        .filter_map(|r| r.as_str().map(String::from))
+                        .collect(),
+                    None => vec![],
+                },
+                filter_from: text("filter_from"),
+                min_size: text("min_size"),
+                max_size: text("max_size"),
+                min_age: text("min_age"),
+                max_age: text("max_age"),
+            }
+        }
+    }
+
+    impl MountOptionsStruct {
+        pub fn new(name: &str) -> MountOptionsStruct {
+            MountOptionsStruct {
+                name: name.to_owned(),
+                mount_point: String::new(),
+                vfs_cache_mode: String::from("full"),
+                allow_other: true,
+                read_only: false,
+                bwlimit: String::new(),
+                extra_flags: vec![],
+                filter: FilterSetStruct::default(),
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ServeOptionsStruct {
+        pub name: String,
+        pub protocol: String,
+        pub addr: String,
+        pub port: String,
+        pub user: String,
+        pub pass: String,
+        pub read_only: bool,
+    }
+
+    /**
+     * A saved copy, sync, bisync or check between two paths, local or
+     * `remote:path`, run by hand or on a schedule
+     */
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct SyncJobStruct {
+        pub name: String,
+        pub source: String,
+        pub destination: String,
+        pub mode: String,
+        pub filter: FilterSetStruct,
+        pub flags: Vec<String>,
+        pub schedule: String,
+        pub condition: String,
+        pub last_run: Option<i64>,
+        pub next_run: Option<i64>,
+    }
+
+    impl ServeOptionsStruct {
+        pub fn new(name: &str) -> ServeOptionsStruct {
+            ServeOptionsStruct {
+                name: name.to_owned(),
+                protocol: String::from("webdav"),
+                addr: String::from("127.0.0.1"),
+                port: String::from("8080"),
+                user: String::new(),
+                pass: String::new(),
+                read_only: false,
+            }
+        }
+    }
+
+    impl SettingsStruct {
+        /**
+         * Stored options of a remote, or the defaults RRclone always mounted with
+         */
+        pub fn stored_mount_options(&self, name: &str) -> MountOptionsStruct {
+            match self.mounts.iter().find(|m| m.name == name) {
+                Some(options) => options.clone(),
+                None => MountOptionsStruct::new(name),
+            }
+        }
+
+        /**
+         * Options a mount is really started with, safe mode forces read-only
+         * and remotes without their own bandwidth limit use the global one
+         */
+        pub fn mount_options(&self, name: &str) -> MountOptionsStruct {
+            let mut options = self.stored_mount_options(name);
+            options.read_only |= self.safe_mode;
+            if options.bwlimit.is_empty() {
+                options.bwlimit = self.bwlimit.clone();
+            }
+            options
+        }
+
+        /**
+         * Why a command may not run, when it writes to a remote marked read-only
+         */
+        pub fn write_problem(&self, args: &[String]) -> Option<String> {
+            written_targets(args).into_iter().find_map(|target| {
+                let (remote, _) = split_target(target);
+                match !remote.is_empty() && self.stored_mount_options(&remote).read_only {
+                    true => Some(format!("{} is marked read-only", remote)),
+                    false => None,
+                }
+            })
+        }
+
+        /**
+         * Why a command may not run, safe mode only lets copies and new folders through
+         */
+        pub fn job_problem(&self, args: &[String]) -> Option<String> {
+            let command = args.first().map_or("", |c| c.as_str());
+            if self.safe_mode && removes_files(command) {
+                return Some(format!("Safe mode is on, {} removes files", command));
+            }
+            self.write_problem(args)
+        }
+
+        pub fn set_mount_options(&mut self, options: &MountOptionsStruct) {
+            match self.mounts.iter().position(|m| m.name == options.name) {
+                Some(i) => self.mounts[i] = options.clone(),
+                None => self.mounts.push(options.clone()),
+            }
+        }
+
+        /**
+         * Last serve options used for a remote, the password is never stored
+         */
+        pub fn serve_options(&self, name: &str) -> ServeOptionsStruct {
+            match self.serves.iter().find(|s| s.name == name) {
+                Some(options) => options.clone(),
+                None => ServeOptionsStruct::new(name),
+            }
+        }
+
+        /**
+         * Follows a renamed remote, or forgets a deleted one with None
+         */
+        pub fn rename_remote(&mut self, name: &str, new_name: Option<&str>) {
+            match new_name {
+                Some(new_name) => {
+                    for options in self.mounts.iter_mut().filter(|m| m.name == name) {
+                        options.name = new_name.to_owned();
+                    }
+                    for options in self.serves.iter_mut().filter(|s| s.name == name) {
+                        options.name = new_name.to_owned();
+                    }
+                    let prefix = format!("{}:", name);
+                    for job in self.syncs.iter_mut() {
+                        for path in [&mut job.source, &mut job.destination] {
+                            if let Some(rest) = path.strip_prefix(&prefix) {
+                                *path = format!("{}:{}", new_name, rest);
+                            }
+                        }
+                    }
+                }
+                None => {
+                    self.mounts.retain(|m| m.name != name);
+                    self.serves.retain(|s| s.name != name);
+                }
+            }
+        }
+
+        /**
+         * Takes the later run times of jobs the other copy has with the same
+         * schedule, a job only moves its runs forward when it ran
+         */
+        pub fn merge_run_times(&mut self, other: &SettingsStruct) {
+            for job in self.syncs.iter_mut() {
+                let theirs = match other
+                    .syncs
+                    .iter()
+                    .find(|s| s.name == job.name && s.schedule == job.schedule)
+                {
+                    Some(theirs) => theirs,
+                    None => continue,
+                };
+                job.last_run = job.last_run.max(theirs.last_run);
+                job.next_run = match (job.next_run, theirs.next_run) {
+                    (Some(mine), Some(theirs)) => Some(mine.max(theirs)),
+                    (mine, theirs) => mine.or(theirs),
+                };
+            }
+        }
+
+        /**
+         * Saves a sync job, `previous` is its name before an edit
+         */
+        pub fn set_sync(
+            &mut self,
+            job: &SyncJobStruct,
+            previous: Option<&str>,
+        ) -> Result<(), String> {
+            let at = previous.and_then(|name| self.syncs.iter().position(|s| s.name == name));
+            let clash = self
+                .syncs
+                .iter()
+                .enumerate()
+                .any(|(i, s)| s.name == job.name && Some(i) != at);
+            if clash {
+                return Err(format!("A sync job named {} exists", job.name));
+            }
+            match at {
+                Some(i) => self.syncs[i] = job.clone(),
+                None => self.syncs.push(job.clone()),
+            }
+            Ok(())
+        }
+
+        pub fn set_serve_options(&mut self, options: &ServeOptionsStruct) {
+            let mut options = options.clone();
+            options.pass = String::new();
+            match self.serves.iter().position(|s| s.name == options.name) {
+                Some(i) => self.serves[i] = options,
+                None => self.serves.push(options),
+            }
+        }
+    }
+
+    pub enum ConfigType {
         RRclone,
         Rclone,
     }
 
-    pub fn read_rclone_config() -> ConfigStruct {
-        let path = match env::consts::OS {
-            "windows" => {
-                let mut path = std::env::var("APPDATA").expect("Can not get AppData folder");
-                path.push_str("/rclone/rclone.conf");
-                path
+    /**
+     * rclone commands that move or delete files somewhere
+     */
+    pub fn removes_files(command: &str) -> bool {
+        matches!(
+            command,
+            "move"
+                | "moveto"
+                | "sync"
+                | "bisync"
+                | "purge"
+                | "delete"
+                | "deletefile"
+                | "rmdir"
+                | "rmdirs"
+        )
+    }
+
+    /**
+     * Targets an rclone command changes, the destination of a copy and both sides of a move
+     */
+    pub fn written_targets(args: &[String]) -> Vec<&str> {
+        let paths: Vec<&str> = args
+            .iter()
+            .skip(1)
+            .take(2)
+            .map(|a| a.as_str())
+            .filter(|a| !a.starts_with('-'))
+            .collect();
+        let command = args.first().map_or("", |c| c.as_str());
+        match command {
+            "copy" | "copyto" | "sync" => paths.into_iter().skip(1).collect(),
+            "bisync" | "move" | "moveto" => paths,
+            "mkdir" | "rmdir" | "rmdirs" | "purge" | "delete" | "deletefile" => {
+                paths.into_iter().take(1).collect()
             }
+            _ => vec![],
+        }
+    }
+
+    pub fn get_config_path(config_type: ConfigType) -> String {
+        let mut path = match env::consts::OS {
+            "windows" => std::env::var("APPDATA").expect("Can not get AppData folder"),
             _ => {
                 let mut path = std::env::var("HOME").expect("Can not get HOME directory");
-                path.push_str("/.config/rclone/rclone.conf");
+                path.push_str("/.config");
                 path
             }
         };
-        let file = File::open(&path).unwrap();
-        let buffered = BufReader::new(file);
-
-        let mut drive_name = String::new();
-        let mut drive_type = String::new();
-        let mut drive_scope = String::new();
-        let mut drive_token: Option<TokenStruct> = None;
+        match config_type {
+            ConfigType::Rclone => path.push_str("/rclone/rclone.conf"),
+            ConfigType::RRclone => path.push_str("/rrclone/rrclone.json"),
+        }
+        path
+    }
 
+    pub fn read_rclone_config() -> ConfigStruct {
+        let path = get_config_path(ConfigType::Rclone);
         let mut drives: Vec<DriveStruct> = Vec::new();
+        // no rclone.conf yet means no remotes
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(e) => {
+                if e.kind() != std::io::ErrorKind::NotFound {
+                    log_error(format!("Couldnt read {}: {}", path, e));
+                }
+                return ConfigStruct { path, drives };
+            }
+        };
+        let buffered = BufReader::new(file);
 
         for line in buffered.lines() {
-            let line = line.unwrap();
-            if line == "" {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => {
+                    log_error(format!("Couldnt read {}: {}", path, e));
+                    break;
+                }
+            };
+            let line = line.trim();
+            if line.starts_with('#') || line.starts_with(';') {
+                continue;
+            }
+            if line.starts_with('[') && line.ends_with(']') {
                 drives.push(DriveStruct {
-                    name: drive_name.clone(),
-                    drive_type: drive_type.clone(),
-                    scope: drive_scope.clone(),
-                    token: match drive_token {
-                        Some(ref val) => val.clone(),
-                        None => panic!("didnt get token for driver"),
-                    },
+                    name: line[1..line.len() - 1].to_owned(),
+                    drive_type: String::new(),
+                    scope: String::new(),
+                    token: None,
+                    options: vec![],
                 });
-            } else if line.starts_with('[') {
-                drive_name = line.get(1..line.len() - 1).unwrap().to_owned();
-            } else {
-                match line.get(..2) {
-                    Some("ty") => {
-                        drive_type = line.split('=').last().unwrap().replace(" ", "").to_owned()
-                    }
-                    Some("sc") => {
-                        drive_scope = line.split('=').last().unwrap().replace(" ", "").to_owned()
-                    }
-                    Some("to") => {
-                        let input = line.split('=').last().unwrap();
+            } else if let Some((key, value)) = line.split_once('=') {
+                let drive = match drives.last_mut() {
+                    Some(drive) => drive,
+                    None => continue,
+                };
+                let value = value.trim();
+                drive
+                    .options
+                    .push((key.trim().to_owned(), value.to_owned()));
+                match key.trim() {
+                    "type" => drive.drive_type = value.to_owned(),
+                    "scope" => drive.scope = value.to_owned(),
+                    "token" => {
                         let json: Value =
-                            serde_json::from_str(&input).expect("couldnt parse token json");
-                        drive_token = Some(TokenStruct {
-                            access_token: json["access_token"].to_string().replace("\"", ""),
-                            token_type: json["token_type"].to_string().replace("\"", ""),
-                            refresh_token: json["refresh_token"].to_string().replace("\"", ""),
+                            serde_json::from_str(value).expect("couldnt parse token json");
+                        drive.token = Some(TokenStruct {
+                            access_token: json["access_token"].to_string().replace('"', ""),
+                            token_type: json["token_type"].to_string().replace('"', ""),
+                            refresh_token: json["refresh_token"].to_string().replace('"', ""),
                             expiry: DateTime::parse_from_rfc3339(
-                                &json["expiry"].to_string().replace("\"", ""),
+                                &json["expiry"].to_string().replace('"', ""),
                             )
                             .unwrap(),
                         });
@@ -104,5 +651,355 @@ pub mod config {
         }
     }
 
-    // pub fn write() {}
+    /**
+     * Writes next to the file first and renames it over, readers never see half a file
+     */
+    pub fn write_atomic(path: &str, content: &str) -> std::io::Result<()> {
+        let tmp = format!("{}.tmp{}", path, std::process::id());
+        fs::write(&tmp, content)?;
+        // rclone.conf holds secrets, the copy keeps its permissions
+        if let Ok(metadata) = fs::metadata(path) {
+            fs::set_permissions(&tmp, metadata.permissions())?;
+        }
+        let renamed = fs::rename(&tmp, path);
+        if renamed.is_err() {
+            let _ = fs::remove_file(&tmp);
+        }
+        renamed
+    }
+
+    /**
+     * Sets keys of one remote in rclone.conf, for secrets that shouldnt go through argv,
+     * every other line stays as it is
+     */
+    pub fn set_rclone_values(
+        path: &str,
+        name: &str,
+        values: &[(String, String)],
+    ) -> std::io::Result<()> {
+        let content = fs::read_to_string(path)?;
+        if content.starts_with("# Encrypted rclone configuration File") {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::Unsupported,
+                "the rclone config is encrypted",
+            ));
+        }
+        let header = format!("[{}]", name);
+        let mut lines: Vec<String> = vec![];
+        let mut inside = false;
+        let mut found = false;
+        for line in content.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with('[') && trimmed.ends_with(']') {
+                inside = trimmed == header;
+                found |= inside;
+                if inside {
+                    lines.push(line.to_owned());
+                    lines.extend(values.iter().map(|(k, v)| format!("{} = {}", k, v)));
+                    continue;
+                }
+            }
+            let replaced = inside
+                && trimmed
+                    .split_once('=')
+                    .is_some_and(|(key, _)| values.iter().any(|(k, _)| k == key.trim()));
+            if !replaced {
+                lines.push(line.to_owned());
+            }
+        }
+        if !found {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("remote {} not found", name),
+            ));
+        }
+        write_atomic(path, &format!("{}\n", lines.join("\n")))
+    }
+
+    pub fn read_rrclone_config() -> SettingsStruct {
+        let path = get_config_path(ConfigType::RRclone);
+        let json: Value = match fs::read_to_string(&path) {
+            Ok(content) => match serde_json::from_str(&content) {
+                Ok(json) => json,
+                Err(e) => {
+                    // keep the broken file around, the next save would overwrite it
+                    let backup = format!("{}.bak", path);
+                    let _ = fs::copy(&path, &backup);
+                    log_error(format!(
+                        "couldnt parse {}, using defaults, old settings kept in {}: {}",
+                        path, backup, e
+                    ));
+                    json!({})
+                }
+            },
+            Err(_) => json!({}),
+        };
+
+        let mounts = match json["mounts"].as_array() {
+            Some(mounts) => mounts
+                .iter()
+                .map(|m| MountOptionsStruct {
+                    name: m["name"].as_str().unwrap_or_default().to_owned(),
+                    mount_point: m["mount_point"].as_str().unwrap_or_default().to_owned(),
+                    vfs_cache_mode: m["vfs_cache_mode"].as_str().unwrap_or("full").to_owned(),
+                    allow_other: m["allow_other"].as_bool().unwrap_or(true),
+                    read_only: m["read_only"].as_bool().unwrap_or(false),
+                    bwlimit: m["bwlimit"].as_str().unwrap_or_default().to_owned(),
+                    extra_flags: match m["extra_flags"].as_array() {
+                        Some(flags) => flags
+                            .iter()
+                            .filter_map(|f| f.as_str().map(|f| f.to_owned()))
+                            .collect(),
+                        None => vec![],
+                    },
+                    filter: FilterSetStruct::from_json(&m["filter"]),
+                })
+                .collect(),
+            None => vec![],
+        };
+
+        let serves = match json["serves"].as_array() {
+            Some(serves) => serves
+                .iter()
+                .map(|s| ServeOptionsStruct {
+                    name: s["name"].as_str().unwrap_or_default().to_owned(),
+                    protocol: s["protocol"].as_str().unwrap_or("webdav").to_owned(),
+                    addr: s["addr"].as_str().unwrap_or("127.0.0.1").to_owned(),
+                    port: s["port"].as_str().unwrap_or("8080").to_owned(),
+                    user: s["user"].as_str().unwrap_or_default().to_owned(),
+                    pass: String::new(),
+                    read_only: s["read_only"].as_bool().unwrap_or(false),
+                })
+                .collect(),
+            None => vec![],
+        };
+
+        let strings = |value: &Value| -> Vec<String> {
+            match value.as_array() {
+                Some(values) => values
+                    .iter()
+                    .filter_map(|v| v.as_str().map(|v| v.to_owned()))
+                    .collect(),
+                None => vec![],
+            }
+        };
+        let syncs = match json["syncs"].as_array() {
+            Some(syncs) => syncs
+                .iter()
+                .map(|s| SyncJobStruct {
+                    name: s["name"].as_str().unwrap_or_default().to_owned(),
+                    source: s["source"].as_str().unwrap_or_default().to_owned(),
+                    destination: s["destination"].as_str().unwrap_or_default().to_owned(),
+                    mode: s["mode"].as_str().unwrap_or("copy").to_owned(),
+                    filter: FilterSetStruct::from_json(&s["filter"]),
+                    flags: strings(&s["flags"]),
+                    schedule: s["schedule"].as_str().unwrap_or_default().to_owned(),
+                    condition: s["condition"].as_str().unwrap_or("always").to_owned(),
+                    last_run: s["last_run"].as_i64(),
+                    next_run: s["next_run"].as_i64(),
+                })
+                .collect(),
+            None => vec![],
+        };
+
+        SettingsStruct {
+            path,
+            safe_mode: json["safe_mode"].as_bool().unwrap_or(false),
+            bwlimit: json["bwlimit"].as_str().unwrap_or_default().to_owned(),
+            headless_auth: json["headless_auth"].as_bool().unwrap_or(false),
+            max_jobs: json["max_jobs"].as_u64().unwrap_or(2).max(1) as usize,
+            quota_warn: json["quota_warn"].as_u64().unwrap_or(90).min(100) as u8,
+            cache_warn_size: json["cache_warn_size"]
+                .as_str()
+                .unwrap_or_default()
+                .to_owned(),
+            mounts,
+            serves,
+            syncs,
+        }
+    }
+
+    pub fn write_rrclone_config(settings: &SettingsStruct) -> std::io::Result<()> {
+        let mounts: Vec<Value> = settings
+            .mounts
+            .iter()
+            .map(|m| {
+                json!({
+                    "name": m.name,
+                    "mount_point": m.mount_point,
+                    "vfs_cache_mode": m.vfs_cache_mode,
+                    "allow_other": m.allow_other,
+                    "read_only": m.read_only,
+                    "bwlimit": m.bwlimit,
+                    "extra_flags": m.extra_flags,
+                    "filter": m.filter.to_json(),
+                })
+            })
+            .collect();
+        let serves: Vec<Value> = settings
+            .serves
+            .iter()
+            .map(|s| {
+                json!({
+                    "name": s.name,
+                    "protocol": s.protocol,
+                    "addr": s.addr,
+                    "port": s.port,
+                    "user": s.user,
+                    "read_only": s.read_only,
+                })
+            })
+            .collect();
+        let syncs: Vec<Value> = settings
+            .syncs
+            .iter()
+            .map(|s| {
+                json!({
+                    "name": s.name,
+                    "source": s.source,
+                    "destination": s.destination,
+                    "mode": s.mode,
+                    "filter": s.filter.to_json(),
+                    "flags": s.flags,
+                    "schedule": s.schedule,
+                    "condition": s.condition,
+                    "last_run": s.last_run,
+                    "next_run": s.next_run,
+                })
+            })
+            .collect();
+        let json = json!({
+            "safe_mode": settings.safe_mode,
+            "bwlimit": settings.bwlimit,
+            "cache_warn_size": settings.cache_warn_size,
+            "headless_auth": settings.headless_auth,
+            "max_jobs": settings.max_jobs,
+            "quota_warn": settings.quota_warn,
+            "mounts": mounts,
+            "serves": serves,
+            "syncs": syncs,
+        });
+
+        if let Some(dir) = std::path::Path::new(&settings.path).parent() {
+            fs::create_dir_all(dir)?;
+        }
+        write_atomic(
+            &settings.path,
+            &serde_json::to_string_pretty(&json).expect("couldnt serialize rrclone settings"),
+        )
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn conf(names: &[&str]) -> ConfigStruct {
+            ConfigStruct {
+                path: String::new(),
+                drives: names
+                    .iter()
+                    .map(|name| DriveStruct {
+                        name: name.to_string(),
+                        drive_type: String::from("drive"),
+                        scope: String::new(),
+                        token: None,
+                        options: vec![],
+                    })
+                    .collect(),
+            }
+        }
+
+        #[test]
+        fn validates_names() {
+            let conf = conf(&["gdrive"]);
+            assert_eq!(conf.validate_name("my-drive_2.backup+x@y"), Ok(()));
+            assert_eq!(conf.validate_name("work docs"), Ok(()));
+            assert!(conf.validate_name("").is_err());
+            assert!(conf.validate_name("a/b").is_err());
+            assert!(conf.validate_name("a:b").is_err());
+            assert!(conf.validate_name("-drive").is_err());
+            assert!(conf.validate_name(" drive").is_err());
+            assert!(conf.validate_name("drive ").is_err());
+            assert_eq!(
+                conf.validate_name("gdrive"),
+                Err(String::from("already exists, try gdrive-2"))
+            );
+        }
+
+        #[test]
+        fn suggests_the_first_free_name() {
+            let conf = conf(&["gdrive", "gdrive-2", "gdrive-3"]);
+            assert_eq!(conf.suggest_name("gdrive"), "gdrive-4");
+            assert_eq!(conf.suggest_name("onedrive"), "onedrive");
+        }
+
+        #[test]
+        fn finds_written_targets() {
+            let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+            assert_eq!(written_targets(&args(&["copy", "a:x", "b:y"])), vec!["b:y"]);
+            assert_eq!(
+                written_targets(&args(&["moveto", "a:x", "b:y"])),
+                vec!["a:x", "b:y"]
+            );
+            assert_eq!(written_targets(&args(&["purge", "a:x"])), vec!["a:x"]);
+            assert!(written_targets(&args(&["check", "a:x", "b:y"])).is_empty());
+        }
+
+        #[test]
+        fn sets_values_of_one_remote_and_keeps_other_lines() {
+            let path = env::temp_dir().join(format!("rrclone-test-{}.conf", std::process::id()));
+            let path = path.to_string_lossy().to_string();
+            fs::write(
+                &path,
+                "# my remotes\n[vault]\ntype = crypt\npassword = old\n\n[gdrive]\ntype = drive\n",
+            )
+            .unwrap();
+            let values = vec![
+                (String::from("password"), String::from("new")),
+                (String::from("password2"), String::from("salt")),
+            ];
+            set_rclone_values(&path, "vault", &values).unwrap();
+            assert_eq!(
+                fs::read_to_string(&path).unwrap(),
+                "# my remotes\n[vault]\npassword = new\npassword2 = salt\ntype = crypt\n\n[gdrive]\ntype = drive\n"
+            );
+            assert!(set_rclone_values(&path, "missing", &values).is_err());
+            fs::remove_file(&path).unwrap();
+        }
+
+        #[test]
+        fn refuses_jobs_safe_mode_or_read_only_forbid() {
+            let args = |args: &[&str]| args.iter().map(|a| a.to_string()).collect::<Vec<_>>();
+            let mut archive = MountOptionsStruct::new("archive");
+            archive.read_only = true;
+            let mut settings = SettingsStruct {
+                path: String::new(),
+                safe_mode: false,
+                bwlimit: String::new(),
+                cache_warn_size: String::new(),
+                headless_auth: false,
+                max_jobs: 2,
+                quota_warn: 90,
+                mounts: vec![archive],
+                serves: vec![],
+                syncs: vec![],
+            };
+            assert!(settings
+                .job_problem(&args(&["copyto", "archive:a", "/tmp/a"]))
+                .is_none());
+            assert!(settings
+                .job_problem(&args(&["copyto", "/tmp/a", "archive:a"]))
+                .is_some());
+            assert!(settings
+                .job_problem(&args(&["moveto", "gdrive:a", "/tmp/a"]))
+                .is_none());
+            settings.safe_mode = true;
+            assert!(settings
+                .job_problem(&args(&["moveto", "gdrive:a", "/tmp/a"]))
+                .is_some());
+            assert!(settings
+                .job_problem(&args(&["copy", "gdrive:a", "/tmp/a"]))
+                .is_none());
+        }
+    }
 }
@@ -0,0 +1,149 @@
+#![allow(dead_code)]
+
+pub mod crypt {
+    use std::{
+        io::Write,
+        process::{Command, Stdio},
+    };
+
+    use rand::{distributions::Alphanumeric, Rng};
+
+    use crate::lib::{
+        config::config::{set_rclone_values, ConfigStruct, DriveStruct},
+        form::form::*,
+        providers::providers::NAME_KEY,
+        utils::utils::run_rclone,
+    };
+
+    pub const FILENAME_MODES: [&str; 3] = ["standard", "obfuscate", "off"];
+
+    /**
+     * Crypt options on top of `drive`, passwords left empty get generated
+     */
+    pub fn crypt_form(conf: &ConfigStruct, drive: &DriveStruct) -> FormStruct {
+        FormStruct::with_fields(vec![
+            FieldStruct::new(
+                NAME_KEY,
+                "Remote name",
+                &conf.suggest_name(&format!("{}-crypt", drive.name)),
+                FieldKind::Text,
+            )
+            .with_help("Name of the encrypted remote"),
+            FieldStruct::new("path", "Path", "encrypted", FieldKind::Text).with_help(&format!(
+                "Folder on {} holding the encrypted files",
+                drive.name
+            )),
+            FieldStruct::new(
+                "filename_encryption",
+                "filename_encryption",
+                FILENAME_MODES[0],
+                FieldKind::Choice(FILENAME_MODES.iter().map(|m| m.to_string()).collect()),
+            )
+            .with_help("standard encrypts names, obfuscate only scrambles them, off keeps them"),
+            FieldStruct::new(
+                "directory_name_encryption",
+                "directory_name_encryption",
+                "true",
+                FieldKind::Toggle,
+            )
+            .with_help("Encrypt folder names too, ignored when filename_encryption is off"),
+            FieldStruct::new("password", "password", "", FieldKind::Secret)
+                .with_help("Empty generates a random password"),
+            FieldStruct::new("password2", "password2 (salt)", "", FieldKind::Secret)
+                .with_help("Empty generates a random salt"),
+        ])
+    }
+
+    pub fn generate_password() -> String {
+        rand::thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(32)
+            .map(char::from)
+            .collect()
+    }
+
+    /**
+     * Fills empty passwords with generated ones shown in clear,
+     * returns true when something was generated
+     */
+    pub fn generate_missing(form: &mut FormStruct) -> bool {
+        let mut generated = false;
+        for field in form
+            .fields
+            .iter_mut()
+            .filter(|f| f.key == "password" || f.key == "password2")
+        {
+            if field.value.is_empty() {
+                field.value = generate_password();
+                field.kind = FieldKind::Text;
+                generated = true;
+            }
+        }
+        generated
+    }
+
+    /**
+     * `rclone obscure -`, the password goes through stdin so it never shows in ps,
+     * the obscured value can be revealed so it must stay off argv too
+     */
+    pub fn obscure(password: &str) -> Result<String, String> {
+        let mut process = Command::new("rclone")
+            .args(["obscure", "-"])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+        if let Some(mut stdin) = process.stdin.take() {
+            stdin
+                .write_all(password.as_bytes())
+                .map_err(|e| e.to_string())?;
+        }
+        let output = process.wait_with_output().map_err(|e| e.to_string())?;
+        match output.status.success() {
+            true => Ok(String::from_utf8_lossy(&output.stdout).trim().to_owned()),
+            false => Err(String::from_utf8_lossy(&output.stderr).trim().to_owned()),
+        }
+    }
+
+    /**
+     * Creates the crypt remote, then writes the obscured passwords into rclone.conf
+     * so neither shows in ps, a remote left without them is deleted again
+     */
+    pub fn create_crypt(
+        conf: &ConfigStruct,
+        drive: &DriveStruct,
+        form: &FormStruct,
+    ) -> Result<String, String> {
+        let name = form.value(NAME_KEY).trim().to_owned();
+        conf.validate_name(&name)?;
+        let path = form.value("path");
+        let mut args = vec![
+            String::from("config"),
+            String::from("create"),
+            name.clone(),
+            String::from("crypt"),
+            String::from("remote"),
+            format!("{}:{}", drive.name, path.trim().trim_start_matches('/')),
+            String::from("filename_encryption"),
+            form.value("filename_encryption"),
+            String::from("directory_name_encryption"),
+            form.value("directory_name_encryption"),
+        ];
+        let mut secrets = vec![];
+        for key in ["password", "password2"] {
+            let password = form.value(key);
+            if password.is_empty() {
+                return Err(format!("{} is empty", key));
+            }
+            secrets.push((key.to_owned(), obscure(&password)?));
+        }
+        args.push(String::from("--non-interactive"));
+        run_rclone(&args)?;
+        if let Err(e) = set_rclone_values(&conf.path, &name, &secrets) {
+            let _ = run_rclone(&[String::from("config"), String::from("delete"), name.clone()]);
+            return Err(format!("couldnt store the passwords of {}: {}", name, e));
+        }
+        Ok(name)
+    }
+}
@@ -0,0 +1,222 @@
+#![allow(dead_code)]
+
+pub mod daemon {
+    use std::{
+        fs::{self, OpenOptions},
+        io::Write,
+        path::{Path, PathBuf},
+        process::{self, Command, Stdio},
+        thread,
+        time::Duration,
+    };
+
+    use chrono::Local;
+    use log::{Level, LevelFilter, Log, Metadata, Record};
+
+    use crate::lib::{
+        config::config::{read_rrclone_config, write_rrclone_config, SettingsStruct},
+        history::history::rrclone_dir,
+        jobs::jobs::{start_queued, JobStruct},
+        schedule::schedule::{due_jobs, now},
+        sync::sync::{sync_args, sync_label},
+        utils::utils::*,
+    };
+
+    /**
+     * Seconds between two looks at the schedules
+     */
+    pub const TICK: i64 = 30;
+
+    /**
+     * Without the TUI logs go to stdout, for journald or a log file
+     */
+    struct StdoutLogger;
+
+    impl Log for StdoutLogger {
+        fn enabled(&self, metadata: &Metadata) -> bool {
+            metadata.level() <= Level::Info
+        }
+
+        fn log(&self, record: &Record) {
+            if self.enabled(record.metadata()) {
+                println!(
+                    "{} {:<5}{}",
+                    Local::now().format("%Y/%m/%d %H:%M:%S"),
+                    record.level(),
+                    record.args()
+                );
+            }
+        }
+
+        fn flush(&self) {}
+    }
+
+    static LOGGER: StdoutLogger = StdoutLogger;
+
+    /**
+     * `<pid> <owner>` of the one process allowed to run the schedules
+     */
+    pub fn lock_path() -> PathBuf {
+        rrclone_dir().join("scheduler.lock")
+    }
+
+    fn pid_alive(pid: u32) -> bool {
+        if Path::new("/proc").is_dir() {
+            return Path::new(&format!("/proc/{}", pid)).exists();
+        }
+        let status = match cfg!(windows) {
+            true => Command::new("tasklist")
+                .args(["/FI", &format!("PID eq {}", pid), "/NH"])
+                .output()
+                .map(|o| String::from_utf8_lossy(&o.stdout).contains(&pid.to_string())),
+            false => Command::new("kill")
+                .args(["-0", &pid.to_string()])
+                .stderr(Stdio::null())
+                .status()
+                .map(|s| s.success()),
+        };
+        status.unwrap_or(false)
+    }
+
+    /**
+     * Pid and owner in the lock file when that process still runs
+     */
+    pub fn scheduler_owner() -> Option<(u32, String)> {
+        let content = fs::read_to_string(lock_path()).ok()?;
+        let (pid, owner) = content.trim().split_once(' ')?;
+        let pid = pid.parse::<u32>().ok()?;
+        pid_alive(pid).then(|| (pid, owner.to_owned()))
+    }
+
+    /**
+     * Held while this process runs the schedules, removed again on drop
+     */
+    #[derive(Debug)]
+    pub struct SchedulerLockStruct {
+        pid: u32,
+    }
+
+    impl SchedulerLockStruct {
+        /**
+         * Takes the lock when nobody alive holds it, or when the holder is one of
+         * `take_over`, the daemon wins over the TUI. Err names the holder
+         */
+        pub fn acquire(owner: &str, take_over: &[&str]) -> Result<SchedulerLockStruct, String> {
+            let pid = process::id();
+            let path = lock_path();
+            if let Some(dir) = path.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            let content = format!("{} {}", pid, owner);
+            match scheduler_owner() {
+                Some((holder, _)) if holder == pid => {}
+                Some((holder, kind)) if take_over.contains(&kind.as_str()) => {
+                    log_info(format!(
+                        "Taking the schedules over from {} {}",
+                        kind, holder
+                    ));
+                    fs::write(&path, content).map_err(|e| e.to_string())?;
+                }
+                Some((holder, kind)) => return Err(format!("{} {}", kind, holder)),
+                None => {
+                    // stale file of a process that died
+                    let _ = fs::remove_file(&path);
+                    OpenOptions::new()
+                        .write(true)
+                        .create_new(true)
+                        .open(&path)
+                        .and_then(|mut file| file.write_all(content.as_bytes()))
+                        .map_err(|e| e.to_string())?;
+                }
+            }
+            Ok(SchedulerLockStruct { pid })
+        }
+
+        /**
+         * False once another process took the schedules over
+         */
+        pub fn held(&self) -> bool {
+            fs::read_to_string(lock_path())
+                .ok()
+                .and_then(|c| c.split_whitespace().next().map(String::from))
+                .is_some_and(|pid| pid == self.pid.to_string())
+        }
+    }
+
+    impl Drop for SchedulerLockStruct {
+        fn drop(&mut self) {
+            if self.held() {
+                let _ = fs::remove_file(lock_path());
+            }
+        }
+    }
+
+    /**
+     * Writes the run times this process changed into the settings on disk,
+     * so edits made meanwhile in the TUI survive
+     */
+    pub fn save_run_times(settings: &SettingsStruct) {
+        let mut current = read_rrclone_config();
+        current.merge_run_times(settings);
+        if let Err(e) = write_rrclone_config(&current) {
+            log_error(format!("Couldnt save settings: {}", e));
+        }
+    }
+
+    /**
+     * Runs scheduled sync jobs until killed, settings are read again on every
+     * tick so jobs edited in the TUI are picked up
+     */
+    pub fn run() {
+        if log::set_logger(&LOGGER).is_ok() {
+            log::set_max_level(LevelFilter::Info);
+        }
+        let lock = match SchedulerLockStruct::acquire("daemon", &["tui"]) {
+            Ok(lock) => lock,
+            Err(holder) => {
+                log_error(format!("{} already runs the schedules", holder));
+                return;
+            }
+        };
+        log_info(String::from("RRclone daemon started"));
+        let mut jobs: Vec<JobStruct> = vec![];
+        let mut next_id = 1;
+        let mut checked = 0;
+        loop {
+            let now = now();
+            if now - checked >= TICK {
+                checked = now;
+                if !lock.held() {
+                    log_error(format!("{} was removed, stopping", lock_path().display()));
+                    break;
+                }
+                let mut settings = read_rrclone_config();
+                let before = settings.syncs.clone();
+                for job in due_jobs(&mut settings, now) {
+                    let label = sync_label(&job);
+                    if jobs.iter().any(|j| j.label == label && !j.state.finished()) {
+                        log_warning(format!("{} is still running, skipped", job.name));
+                        continue;
+                    }
+                    jobs.push(JobStruct::new(next_id, &label, sync_args(&job)));
+                    next_id += 1;
+                }
+                if settings.syncs != before {
+                    save_run_times(&settings);
+                }
+                start_queued(&mut jobs, settings.max_jobs);
+            }
+            let mut finished = false;
+            for job in jobs.iter_mut() {
+                finished |= job.poll();
+            }
+            // finished jobs are in the history already
+            jobs.retain(|j| !j.state.finished());
+            if finished {
+                let settings = read_rrclone_config();
+                start_queued(&mut jobs, settings.max_jobs);
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+    }
+}
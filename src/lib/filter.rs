@@ -0,0 +1,452 @@
+#![allow(dead_code)]
+
+pub mod filter {
+    use std::collections::HashSet;
+
+    use chrono::{NaiveDate, NaiveDateTime};
+
+    use crate::lib::{
+        app::app_mod::StatefulList,
+        browser::browser::{start_listing_with, FileStruct, ListingStruct},
+        config::config::FilterSetStruct,
+        form::form::*,
+        picker::picker::expand_home,
+        utils::utils::*,
+    };
+
+    /**
+     * rclone arguments of a rule set, nothing for an empty one
+     */
+    pub fn filter_args(set: &FilterSetStruct) -> Vec<String> {
+        let mut args = vec![];
+        for rule in set.rules.iter() {
+            args.push(String::from("--filter"));
+            args.push(rule.clone());
+        }
+        if !set.filter_from.trim().is_empty() {
+            args.push(String::from("--filter-from"));
+            let path = expand_home(set.filter_from.trim());
+            args.push(path.to_string_lossy().to_string());
+        }
+        let options = [
+            ("--min-size", &set.min_size),
+            ("--max-size", &set.max_size),
+            ("--min-age", &set.min_age),
+            ("--max-age", &set.max_age),
+        ];
+        for (flag, value) in options {
+            if !value.trim().is_empty() {
+                args.push(String::from(flag));
+                args.push(value.trim().to_owned());
+            }
+        }
+        args
+    }
+
+    /**
+     * `+ glob`, `- glob` or `!` which clears the rules before it
+     */
+    pub fn rule_problem(rule: &str) -> Option<String> {
+        if rule == "!" {
+            return None;
+        }
+        let pattern = match rule.strip_prefix("+ ").or(rule.strip_prefix("- ")) {
+            Some(pattern) => pattern,
+            None => return Some(format!("'{}' must start with '+ ' or '- '", rule)),
+        };
+        if pattern.trim().is_empty() {
+            return Some(format!("'{}' has no pattern", rule));
+        }
+        if pattern != pattern.trim() {
+            return Some(format!("'{}' has spaces around the pattern", rule));
+        }
+        // rclone globs dont nest braces or brackets
+        let mut brace = false;
+        let mut bracket = false;
+        for c in pattern.chars() {
+            match c {
+                '{' if brace => return Some(format!("'{}' nests braces", rule)),
+                '{' => brace = true,
+                '}' if !brace => return Some(format!("'{}' closes a brace never opened", rule)),
+                '}' => brace = false,
+                '[' if !bracket => bracket = true,
+                ']' if bracket => bracket = false,
+                _ => {}
+            }
+        }
+        match (brace, bracket) {
+            (true, _) => Some(format!("'{}' leaves a brace open", rule)),
+            (_, true) => Some(format!("'{}' leaves a bracket open", rule)),
+            _ => None,
+        }
+    }
+
+    /**
+     * `7d`, `1h30m`, `2w`, `1M`, `1y` or a date
+     */
+    pub fn valid_age(age: &str) -> bool {
+        if NaiveDate::parse_from_str(age, "%Y-%m-%d").is_ok()
+            || NaiveDateTime::parse_from_str(age, "%Y-%m-%d %H:%M:%S").is_ok()
+        {
+            return true;
+        }
+        let mut rest = age;
+        while !rest.is_empty() {
+            let number = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(rest.len());
+            if number == 0 || rest[..number].parse::<f64>().is_err() {
+                return false;
+            }
+            rest = &rest[number..];
+            let unit = ["ms", "s", "m", "h", "d", "w", "M", "y"]
+                .into_iter()
+                .find(|u| rest.starts_with(u));
+            match unit {
+                Some(unit) => rest = &rest[unit.len()..],
+                // a plain number is seconds
+                None if rest.is_empty() && number == age.len() => return true,
+                None => return false,
+            }
+        }
+        true
+    }
+
+    /**
+     * Everything wrong or suspicious in a set, empty when rclone will take it
+     */
+    pub fn set_problems(set: &FilterSetStruct) -> Vec<String> {
+        let mut problems: Vec<String> = set.rules.iter().filter_map(|r| rule_problem(r)).collect();
+        let mut catch_all: Option<&str> = None;
+        for rule in set.rules.iter() {
+            match (catch_all, rule.as_str()) {
+                (_, "!") => catch_all = None,
+                (Some(all), rule) => problems.push(format!(
+                    "'{}' comes after '{}' and never matches",
+                    rule, all
+                )),
+                (None, all @ ("- **" | "+ **")) => catch_all = Some(all),
+                _ => {}
+            }
+        }
+        if !set.filter_from.is_empty() && !expand_home(&set.filter_from).is_file() {
+            problems.push(format!("{} isnt a file", set.filter_from));
+        }
+        for (label, size) in [("Min size", &set.min_size), ("Max size", &set.max_size)] {
+            if !size.is_empty() && parse_size(size).is_none() {
+                problems.push(format!("{} '{}' isnt a size like 10M", label, size));
+            }
+        }
+        for (label, age) in [("Min age", &set.min_age), ("Max age", &set.max_age)] {
+            if !age.is_empty() && !valid_age(age) {
+                problems.push(format!(
+                    "{} '{}' isnt an age like 7d or 2024-01-31",
+                    label, age
+                ));
+            }
+        }
+        problems
+    }
+
+    pub fn options_form(set: &FilterSetStruct) -> FormStruct {
+        FormStruct::with_fields(vec![
+            FieldStruct::new(
+                "filter_from",
+                "Filter from",
+                &set.filter_from,
+                FieldKind::Text,
+            )
+            .with_help("A file of rules, one per line, read after the rules above"),
+            FieldStruct::new("min_size", "Min size", &set.min_size, FieldKind::Text)
+                .with_help("Skip smaller files, e.g. 100k"),
+            FieldStruct::new("max_size", "Max size", &set.max_size, FieldKind::Text)
+                .with_help("Skip bigger files, e.g. 2G"),
+            FieldStruct::new("min_age", "Min age", &set.min_age, FieldKind::Text)
+                .with_help("Skip files newer than this, e.g. 1d"),
+            FieldStruct::new("max_age", "Max age", &set.max_age, FieldKind::Text)
+                .with_help("Skip files older than this, e.g. 30d or 2024-01-31"),
+        ])
+    }
+
+    /**
+     * `remote:path` or a local folder, as the remote and path lsjson wants
+     */
+    pub fn split_target(target: &str) -> (String, String) {
+        match target.split_once(':') {
+            // C:\ is a drive letter, not a remote
+            Some((remote, path)) if remote.len() > 1 && !remote.contains('/') => {
+                (remote.to_owned(), path.to_owned())
+            }
+            _ => (String::new(), target.to_owned()),
+        }
+    }
+
+    /**
+     * Lists a folder twice, without and with the rules, two levels deep
+     */
+    pub struct FilterPreviewStruct {
+        pub target: String,
+        pub files: Vec<FileStruct>,
+        pub matched: HashSet<String>,
+        all: ListingStruct,
+        filtered: ListingStruct,
+    }
+
+    impl FilterPreviewStruct {
+        pub fn start(target: &str, set: &FilterSetStruct) -> Result<FilterPreviewStruct, String> {
+            let (remote, path) = split_target(target);
+            let depth = [
+                String::from("-R"),
+                String::from("--max-depth"),
+                String::from("2"),
+            ];
+            let mut filtered = depth.to_vec();
+            filtered.extend(filter_args(set));
+            let mut all = start_listing_with(&remote, &path, &depth)?;
+            let filtered = match start_listing_with(&remote, &path, &filtered) {
+                Ok(filtered) => filtered,
+                Err(e) => {
+                    all.cancel();
+                    return Err(e);
+                }
+            };
+            Ok(FilterPreviewStruct {
+                target: target.to_owned(),
+                files: vec![],
+                matched: HashSet::new(),
+                all,
+                filtered,
+            })
+        }
+
+        pub fn poll(&mut self) {
+            self.files.extend(self.all.poll());
+            self.matched
+                .extend(self.filtered.poll().into_iter().map(|f| f.path));
+        }
+
+        pub fn done(&self) -> bool {
+            self.all.done && self.filtered.done
+        }
+
+        pub fn failed(&self) -> bool {
+            self.all.failed || self.filtered.failed
+        }
+
+        pub fn cancel(&mut self) {
+            self.all.cancel();
+            self.filtered.cancel();
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum FilterTarget {
+        Mount(String),
+        Sync(String),
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum FilterInput {
+        Include,
+        Exclude,
+        Edit(usize),
+        PreviewDir,
+    }
+
+    /**
+     * State of the filter editor screen
+     */
+    pub struct FilterEditorStruct {
+        pub target: FilterTarget,
+        pub rules: StatefulList<String>,
+        pub form: FormStruct,
+        pub focus_rules: bool,
+        pub input: Option<FilterInput>,
+        pub input_text: String,
+        pub preview_dir: String,
+        pub preview: Option<FilterPreviewStruct>,
+    }
+
+    impl FilterEditorStruct {
+        pub fn new(
+            target: FilterTarget,
+            set: &FilterSetStruct,
+            preview_dir: &str,
+        ) -> FilterEditorStruct {
+            let mut rules = StatefulList::with_items(&set.rules);
+            if !set.rules.is_empty() {
+                rules.state.select(Some(0));
+            }
+            FilterEditorStruct {
+                target,
+                rules,
+                form: options_form(set),
+                focus_rules: true,
+                input: None,
+                input_text: String::new(),
+                preview_dir: preview_dir.to_owned(),
+                preview: None,
+            }
+        }
+
+        pub fn title(&self) -> String {
+            match &self.target {
+                FilterTarget::Mount(name) => format!("Filters of the {} mount", name),
+                FilterTarget::Sync(name) => format!("Filters of the {} sync job", name),
+            }
+        }
+
+        pub fn set(&self) -> FilterSetStruct {
+            FilterSetStruct {
+                rules: self.rules.items.clone(),
+                filter_from: self.form.value("filter_from").trim().to_owned(),
+                min_size: self.form.value("min_size").trim().to_owned(),
+                max_size: self.form.value("max_size").trim().to_owned(),
+                min_age: self.form.value("min_age").trim().to_owned(),
+                max_age: self.form.value("max_age").trim().to_owned(),
+            }
+        }
+
+        pub fn problems(&self) -> Vec<String> {
+            set_problems(&self.set())
+        }
+
+        /**
+         * Lists the preview folder again with the current rules, only valid ones
+         */
+        pub fn refresh_preview(&mut self) -> Result<(), String> {
+            if let Some(preview) = self.preview.as_mut() {
+                preview.cancel();
+            }
+            self.preview = None;
+            if let Some(problem) = self.problems().first() {
+                return Err(problem.clone());
+            }
+            self.preview = Some(FilterPreviewStruct::start(&self.preview_dir, &self.set())?);
+            Ok(())
+        }
+
+        /**
+         * Applies the input line, rules are checked before they are added
+         */
+        pub fn submit(&mut self) -> Result<(), String> {
+            let text = self.input_text.trim().to_owned();
+            let input = match self.input.take() {
+                Some(input) => input,
+                None => return Ok(()),
+            };
+            let rule = match input {
+                FilterInput::PreviewDir => {
+                    self.preview_dir = text;
+                    return self.refresh_preview();
+                }
+                // `!` is its own rule, whatever key added it
+                _ if text == "!" => text,
+                FilterInput::Include if !text.starts_with("+ ") => format!("+ {}", text),
+                FilterInput::Exclude if !text.starts_with("- ") => format!("- {}", text),
+                _ => text,
+            };
+            if let Some(problem) = rule_problem(&rule) {
+                self.input = Some(input);
+                return Err(problem);
+            }
+            match input {
+                FilterInput::Edit(i) => self.rules.items[i] = rule,
+                _ => {
+                    let i = self
+                        .rules
+                        .state
+                        .selected()
+                        .map_or(self.rules.items.len(), |i| i + 1);
+                    self.rules.items.insert(i, rule);
+                    self.rules.state.select(Some(i));
+                }
+            }
+            self.refresh_preview()
+        }
+
+        /**
+         * Moves the selected rule, rclone uses the first rule that matches
+         */
+        pub fn move_rule(&mut self, up: bool) {
+            let i = match self.rules.state.selected() {
+                Some(i) => i,
+                None => return,
+            };
+            let j = match up {
+                true if i > 0 => i - 1,
+                false if i + 1 < self.rules.items.len() => i + 1,
+                _ => return,
+            };
+            self.rules.items.swap(i, j);
+            self.rules.state.select(Some(j));
+        }
+
+        pub fn remove_rule(&mut self) {
+            if let Some(i) = self.rules.state.selected() {
+                if i < self.rules.items.len() {
+                    self.rules.items.remove(i);
+                }
+                self.rules.state.select(match self.rules.items.is_empty() {
+                    true => None,
+                    false => Some(i.min(self.rules.items.len() - 1)),
+                });
+            }
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn accepts_valid_rules() {
+            for rule in [
+                "+ *.jpg",
+                "- /tmp/**",
+                "- *.{bak,tmp}",
+                "+ file[0-9].txt",
+                "!",
+            ] {
+                assert_eq!(rule_problem(rule), None, "{}", rule);
+            }
+        }
+
+        #[test]
+        fn explains_bad_rules() {
+            for rule in [
+                "*.jpg",
+                "+ ",
+                "+  *.jpg",
+                "- *.{a,{b}}",
+                "- a}",
+                "- *.{bak",
+                "+ file[0-9",
+            ] {
+                assert!(rule_problem(rule).is_some(), "{}", rule);
+            }
+        }
+
+        #[test]
+        fn checks_ages() {
+            for age in ["7d", "1h30m", "2w", "1M", "1y", "1.5d", "300", "2024-01-31"] {
+                assert!(valid_age(age), "{}", age);
+            }
+            for age in ["d", "7x", "1h30", "2024-13-01", "-1d"] {
+                assert!(!valid_age(age), "{}", age);
+            }
+        }
+
+        #[test]
+        fn expands_home_in_filter_from() {
+            let set = FilterSetStruct {
+                filter_from: String::from("~/rules.txt"),
+                ..Default::default()
+            };
+            let args = filter_args(&set);
+            assert_eq!(args[0], "--filter-from");
+            assert!(!args[1].starts_with('~'));
+            assert!(args[1].ends_with("/rules.txt"));
+        }
+    }
+}
@@ -0,0 +1,163 @@
+#![allow(dead_code)]
+
+pub mod form {
+    use crossterm::event::KeyCode;
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum FieldKind {
+        Text,
+        Secret,
+        Toggle,
+        Choice(Vec<String>),
+    }
+
+    #[derive(Debug, Clone)]
+    pub struct FieldStruct {
+        pub key: String,
+        pub label: String,
+        pub value: String,
+        pub kind: FieldKind,
+        pub help: String,
+        /**
+         * Set once the value changed after the form was built
+         */
+        pub edited: bool,
+    }
+
+    #[derive(Debug, Clone, Default)]
+    pub struct FormStruct {
+        pub fields: Vec<FieldStruct>,
+        pub selected: usize,
+    }
+
+    impl FieldStruct {
+        pub fn new(key: &str, label: &str, value: &str, kind: FieldKind) -> FieldStruct {
+            FieldStruct {
+                key: key.to_owned(),
+                label: label.to_owned(),
+                value: value.to_owned(),
+                kind,
+                help: String::new(),
+                edited: false,
+            }
+        }
+
+        pub fn with_help(mut self, help: &str) -> FieldStruct {
+            self.help = help.to_owned();
+            self
+        }
+
+        /**
+         * Value as it should be rendered, secrets are masked
+         */
+        pub fn display(&self) -> String {
+            match self.kind {
+                FieldKind::Secret => "*".repeat(self.value.chars().count()),
+                FieldKind::Toggle => match self.value.as_str() {
+                    "true" => String::from("[x]"),
+                    _ => String::from("[ ]"),
+                },
+                FieldKind::Choice(_) => format!("< {} >", self.value),
+                FieldKind::Text => self.value.clone(),
+            }
+        }
+    }
+
+    impl FormStruct {
+        pub fn with_fields(fields: Vec<FieldStruct>) -> FormStruct {
+            FormStruct {
+                fields,
+                selected: 0,
+            }
+        }
+
+        pub fn next(&mut self) {
+            if !self.fields.is_empty() {
+                self.selected = (self.selected + 1) % self.fields.len();
+            }
+        }
+
+        pub fn previous(&mut self) {
+            if !self.fields.is_empty() {
+                self.selected = (self.selected + self.fields.len() - 1) % self.fields.len();
+            }
+        }
+
+        pub fn value(&self, key: &str) -> String {
+            match self.fields.iter().find(|f| f.key == key) {
+                Some(field) => field.value.clone(),
+                None => String::new(),
+            }
+        }
+
+        pub fn selected_field(&self) -> Option<&FieldStruct> {
+            self.fields.get(self.selected)
+        }
+
+        pub fn toggled(&self, key: &str) -> bool {
+            self.value(key) == "true"
+        }
+
+        pub fn set(&mut self, key: &str, value: &str) {
+            if let Some(field) = self.fields.iter_mut().find(|f| f.key == key) {
+                field.edited |= field.value != value;
+                field.value = value.to_owned();
+            }
+        }
+
+        /**
+         * Edits the selected field, returns false if the key wasnt used
+         */
+        pub fn on_key(&mut self, code: KeyCode) -> bool {
+            match code {
+                KeyCode::Down | KeyCode::Tab => self.next(),
+                KeyCode::Up | KeyCode::BackTab => self.previous(),
+                _ => {
+                    let field = match self.fields.get_mut(self.selected) {
+                        Some(field) => field,
+                        None => return false,
+                    };
+                    let before = field.value.clone();
+                    // a stored secret is obscured, typing replaces it instead of appending
+                    if field.kind == FieldKind::Secret
+                        && !field.edited
+                        && matches!(code, KeyCode::Char(_) | KeyCode::Backspace)
+                    {
+                        field.value.clear();
+                    }
+                    match (&field.kind, code) {
+                        (FieldKind::Text | FieldKind::Secret, KeyCode::Char(c)) => {
+                            field.value.push(c)
+                        }
+                        (FieldKind::Text | FieldKind::Secret, KeyCode::Backspace) => {
+                            field.value.pop();
+                        }
+                        (
+                            FieldKind::Toggle,
+                            KeyCode::Char(' ') | KeyCode::Left | KeyCode::Right,
+                        ) => {
+                            field.value = match field.value.as_str() {
+                                "true" => String::from("false"),
+                                _ => String::from("true"),
+                            }
+                        }
+                        (FieldKind::Choice(choices), KeyCode::Left | KeyCode::Right) => {
+                            if choices.is_empty() {
+                                return true;
+                            }
+                            let i = choices.iter().position(|c| c == &field.value).unwrap_or(0);
+                            let i = match code {
+                                KeyCode::Left => (i + choices.len() - 1) % choices.len(),
+                                _ => (i + 1) % choices.len(),
+                            };
+                            field.value = choices[i].clone();
+                        }
+                        _ => return false,
+                    }
+                    field.edited |= field.value != before;
+                }
+            }
+            true
+        }
+    }
+}
@@ -0,0 +1,200 @@
+#![allow(dead_code)]
+
+pub mod fstab {
+    use std::{fs, io, path::Path};
+
+    use crate::lib::{
+        config::config::{get_config_path, ConfigType, DriveStruct, MountOptionsStruct},
+        mount::mount::{get_mount_point, mount_flags},
+    };
+
+    /**
+     * fstab and autofs split fields on whitespace, `\040` keeps a space inside one
+     */
+    pub fn escape_field(field: &str) -> String {
+        field.replace(' ', "\\040").replace('\t', "\\011")
+    }
+
+    /**
+     * Turns rclone mount flags into mount helper options
+     * `--vfs-cache-mode full --allow-other` -> `vfs_cache_mode=full,allow_other`
+     * values with a comma are refused, the helper would split them into two options
+     */
+    pub fn helper_options(flags: &[String]) -> Result<Vec<String>, String> {
+        let mut options: Vec<String> = vec![];
+        let mut iter = flags.iter().peekable();
+        while let Some(flag) = iter.next() {
+            let key = flag.trim_start_matches('-').replace('-', "_");
+            let option = match key.split_once('=') {
+                Some((key, value)) => format!("{}={}", key, value),
+                None => match iter.peek() {
+                    Some(value) if !value.starts_with("--") => {
+                        format!("{}={}", key, iter.next().unwrap())
+                    }
+                    _ => key,
+                },
+            };
+            if option.contains(',') {
+                return Err(format!(
+                    "{} holds a comma, mount options cant carry it",
+                    option
+                ));
+            }
+            options.push(escape_field(&option));
+        }
+        Ok(options)
+    }
+
+    pub fn fstab_entry(
+        drive: &DriveStruct,
+        options: &MountOptionsStruct,
+    ) -> Result<String, String> {
+        let mut helper = vec![
+            String::from(if options.read_only { "ro" } else { "rw" }),
+            String::from("noauto"),
+            String::from("nofail"),
+            String::from("_netdev"),
+            String::from("x-systemd.automount"),
+            String::from("args2env"),
+            format!(
+                "config={}",
+                escape_field(&get_config_path(ConfigType::Rclone))
+            ),
+        ];
+        helper.extend(helper_options(&mount_flags(options))?);
+        Ok(format!(
+            "{}: {} rclone {} 0 0",
+            escape_field(&drive.name),
+            escape_field(&get_mount_point(drive, options)),
+            helper.join(",")
+        ))
+    }
+
+    pub fn autofs_entry(
+        drive: &DriveStruct,
+        options: &MountOptionsStruct,
+    ) -> Result<String, String> {
+        let mut helper = vec![
+            String::from("-fstype=rclone"),
+            String::from(if options.read_only { "ro" } else { "rw" }),
+            format!(
+                "config={}",
+                escape_field(&get_config_path(ConfigType::Rclone))
+            ),
+        ];
+        helper.extend(helper_options(&mount_flags(options))?);
+        let name = escape_field(&drive.name);
+        Ok(format!("{} {} :{}:", name, helper.join(","), name))
+    }
+
+    /**
+     * Writes `<name>.fstab` and `<name>.autofs` next to RRclone settings
+     */
+    pub fn write_entries(drive: &DriveStruct, entries: &[String]) -> io::Result<String> {
+        let (fstab_line, autofs_line) = match entries {
+            [fstab, autofs] => (fstab, autofs),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "expected an fstab and an autofs entry",
+                ))
+            }
+        };
+        let path = get_config_path(ConfigType::RRclone);
+        let dir = match Path::new(&path).parent() {
+            Some(dir) => dir,
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} has no parent folder", path),
+                ))
+            }
+        };
+        fs::create_dir_all(dir)?;
+        let fstab = dir.join(format!("{}.fstab", drive.name));
+        let autofs = dir.join(format!("{}.autofs", drive.name));
+        fs::write(&fstab, format!("{}\n", fstab_line))?;
+        fs::write(&autofs, format!("{}\n", autofs_line))?;
+        Ok(dir.to_string_lossy().to_string())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn flags(flags: &[&str]) -> Vec<String> {
+            flags.iter().map(|f| f.to_string()).collect()
+        }
+
+        fn drive(name: &str) -> DriveStruct {
+            DriveStruct {
+                name: name.to_owned(),
+                drive_type: String::from("drive"),
+                scope: String::new(),
+                token: None,
+                options: vec![],
+            }
+        }
+
+        #[test]
+        fn turns_flags_into_helper_options() {
+            let options = helper_options(&flags(&[
+                "--vfs-cache-mode",
+                "full",
+                "--allow-other",
+                "--dir-cache-time=5m",
+            ]));
+            assert_eq!(
+                options,
+                Ok(flags(&[
+                    "vfs_cache_mode=full",
+                    "allow_other",
+                    "dir_cache_time=5m"
+                ]))
+            );
+        }
+
+        #[test]
+        fn refuses_values_with_commas() {
+            assert!(helper_options(&flags(&["--bwlimit", "08:00,512k"])).is_err());
+        }
+
+        #[test]
+        fn escapes_spaces() {
+            assert_eq!(escape_field("My Drive"), "My\\040Drive");
+            assert_eq!(
+                helper_options(&flags(&["--include", "a b"])),
+                Ok(flags(&["include=a\\040b"]))
+            );
+        }
+
+        #[test]
+        fn builds_fstab_and_autofs_lines() {
+            let mut options = MountOptionsStruct::new("work docs");
+            options.mount_point = String::from("/mnt/work docs");
+            options.read_only = true;
+            let fstab = fstab_entry(&drive("work docs"), &options).unwrap();
+            assert!(fstab.starts_with("work\\040docs: /mnt/work\\040docs rclone ro,noauto,"));
+            assert!(fstab.ends_with(" 0 0"));
+            assert_eq!(fstab.split_whitespace().count(), 6);
+            let autofs = autofs_entry(&drive("work docs"), &options).unwrap();
+            assert!(autofs.starts_with("work\\040docs -fstype=rclone,ro,"));
+            assert!(autofs.ends_with(" :work\\040docs:"));
+        }
+
+        #[test]
+        fn entries_with_comma_options_fail() {
+            let mut options = MountOptionsStruct::new("gdrive");
+            options.mount_point = String::from("/mnt/gdrive");
+            options.bwlimit = String::from("08:00,512k 23:00,off");
+            assert!(fstab_entry(&drive("gdrive"), &options).is_err());
+            assert!(autofs_entry(&drive("gdrive"), &options).is_err());
+        }
+
+        #[test]
+        fn write_entries_needs_both_lines() {
+            let result = write_entries(&drive("gdrive"), &[String::from("only one")]);
+            assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+        }
+    }
+}
@@ -0,0 +1,259 @@
+#![allow(dead_code)]
+
+pub mod gdrive {
+    use serde_json::Value;
+
+    use std::{
+        fs,
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    use crate::lib::{
+        config::config::DriveStruct, form::form::*, picker::picker::expand_home,
+        remote::remote::ADD_KEY, utils::utils::run_rclone,
+    };
+
+    pub const SCOPES: [&str; 5] = [
+        "drive",
+        "drive.readonly",
+        "drive.file",
+        "drive.appfolder",
+        "drive.metadata.readonly",
+    ];
+
+    const MY_DRIVE: &str = "My Drive";
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct SharedDriveStruct {
+        pub id: String,
+        pub name: String,
+    }
+
+    /**
+     * Shared drives the remote can see, from `rclone backend drives`
+     */
+    pub fn shared_drives(name: &str) -> Result<Vec<SharedDriveStruct>, String> {
+        let output = run_rclone(&[
+            String::from("backend"),
+            String::from("drives"),
+            format!("{}:", name),
+        ])?;
+        let json: Value = serde_json::from_str(&output).map_err(|e| e.to_string())?;
+        Ok(json
+            .as_array()
+            .ok_or("unexpected rclone backend drives output")?
+            .iter()
+            .map(|d| SharedDriveStruct {
+                id: d["id"].as_str().unwrap_or_default().to_owned(),
+                name: d["name"].as_str().unwrap_or_default().to_owned(),
+            })
+            .filter(|d| !d.id.is_empty())
+            .collect())
+    }
+
+    /**
+     * `rclone backend drives` in the background, for the edit form or right after a login
+     */
+    pub struct SharedFetchStruct {
+        pub name: String,
+        pub added: bool,
+        rx: Receiver<Result<Vec<SharedDriveStruct>, String>>,
+    }
+
+    impl SharedFetchStruct {
+        pub fn start(name: &str, added: bool) -> SharedFetchStruct {
+            let (tx, rx) = mpsc::channel();
+            let remote = name.to_owned();
+            thread::spawn(move || {
+                let _ = tx.send(shared_drives(&remote));
+            });
+            SharedFetchStruct {
+                name: name.to_owned(),
+                added,
+                rx,
+            }
+        }
+
+        /**
+         * The shared drives once rclone answered
+         */
+        pub fn poll(&self) -> Option<Result<Vec<SharedDriveStruct>, String>> {
+            match self.rx.try_recv() {
+                Ok(shared) => Some(shared),
+                Err(mpsc::TryRecvError::Empty) => None,
+                Err(mpsc::TryRecvError::Disconnected) => {
+                    Some(Err(String::from("rclone backend drives died")))
+                }
+            }
+        }
+    }
+
+    fn replace_field(form: &mut FormStruct, field: FieldStruct) {
+        match form.fields.iter().position(|f| f.key == field.key) {
+            Some(i) => form.fields[i] = field,
+            // before the "+ new option" field of the edit form
+            None => match form.fields.last() {
+                Some(last) if last.key == ADD_KEY => {
+                    let at = form.fields.len() - 1;
+                    form.fields.insert(at, field)
+                }
+                _ => form.fields.push(field),
+            },
+        }
+    }
+
+    /**
+     * Scope and root folder fields, a shared drive picker when some are known
+     */
+    pub fn drive_fields(
+        form: &mut FormStruct,
+        drive: Option<&DriveStruct>,
+        shared: &[SharedDriveStruct],
+    ) {
+        let current = |key: &str| {
+            drive
+                .and_then(|d| d.option(key))
+                .unwrap_or_default()
+                .to_owned()
+        };
+
+        let mut scopes: Vec<String> = SCOPES.iter().map(|s| s.to_string()).collect();
+        let scope = match current("scope") {
+            scope if scope.is_empty() => SCOPES[0].to_owned(),
+            scope => scope,
+        };
+        if !scopes.contains(&scope) {
+            scopes.push(scope.clone());
+        }
+        let mut help = String::from("drive.file only sees files rclone created");
+        if drive.is_some() {
+            help.push_str(", changing it needs a new login");
+        }
+        replace_field(
+            form,
+            FieldStruct::new("scope", "scope", &scope, FieldKind::Choice(scopes)).with_help(&help),
+        );
+        replace_field(
+            form,
+            FieldStruct::new(
+                "root_folder_id",
+                "root_folder_id",
+                &current("root_folder_id"),
+                FieldKind::Text,
+            )
+            .with_help("Folder id from the Drive URL to use as root, empty for the whole drive"),
+        );
+        replace_field(
+            form,
+            FieldStruct::new(
+                "service_account_file",
+                "service_account_file",
+                &current("service_account_file"),
+                FieldKind::Text,
+            )
+            .with_help("Service account JSON instead of a login, Ctrl+O browses files"),
+        );
+        replace_field(
+            form,
+            FieldStruct::new(
+                "impersonate",
+                "impersonate",
+                &current("impersonate"),
+                FieldKind::Text,
+            )
+            .with_help("User the service account acts as, needs domain-wide delegation"),
+        );
+
+        if let Some(drive) = drive {
+            shared_drive_field(form, drive, shared);
+        }
+    }
+
+    /**
+     * Picker of the shared drives, the one in use stays selected
+     */
+    pub fn shared_drive_field(
+        form: &mut FormStruct,
+        drive: &DriveStruct,
+        shared: &[SharedDriveStruct],
+    ) {
+        let team_drive = drive.option("team_drive").unwrap_or_default().to_owned();
+        let mut choices = vec![MY_DRIVE.to_owned()];
+        choices.extend(shared.iter().map(|d| format!("{} [{}]", d.name, d.id)));
+        let value = match shared.iter().find(|d| d.id == team_drive) {
+            Some(d) => format!("{} [{}]", d.name, d.id),
+            None if team_drive.is_empty() => MY_DRIVE.to_owned(),
+            None => {
+                choices.push(team_drive.clone());
+                team_drive
+            }
+        };
+        replace_field(
+            form,
+            FieldStruct::new(
+                "team_drive",
+                "shared drive",
+                &value,
+                FieldKind::Choice(choices),
+            )
+            .with_help("Left/Right picks a shared drive"),
+        );
+    }
+
+    /**
+     * Turns the shared drive picked by name back into its id
+     */
+    pub fn normalize_drive_form(form: &FormStruct) -> FormStruct {
+        let mut form = form.clone();
+        let picked = form.value("team_drive");
+        let id = match picked.as_str() {
+            MY_DRIVE => String::new(),
+            _ => match picked.rsplit_once(" [") {
+                Some((_, id)) => id.trim_end_matches(']').to_owned(),
+                None => picked.clone(),
+            },
+        };
+        form.set("team_drive", &id);
+        form
+    }
+
+    /**
+     * `key value` pairs for a new Drive remote, defaults left out
+     */
+    pub fn drive_params(form: &FormStruct) -> Vec<String> {
+        let mut params: Vec<String> = vec![];
+        let scope = form.value("scope");
+        if scope != SCOPES[0] {
+            params.push(String::from("scope"));
+            params.push(scope);
+        }
+        for key in ["root_folder_id", "service_account_file", "impersonate"] {
+            let value = form.value(key);
+            if !value.trim().is_empty() {
+                params.push(key.to_owned());
+                params.push(value.trim().to_owned());
+            }
+        }
+        params
+    }
+
+    /**
+     * Checks the service account fields, Ok with the file path expanded
+     */
+    pub fn validate_service_account(form: &FormStruct) -> Result<String, String> {
+        let path = expand_home(form.value("service_account_file").trim());
+        let content =
+            fs::read_to_string(&path).map_err(|e| format!("{}: {}", path.display(), e))?;
+        let json: Value = serde_json::from_str(&content)
+            .map_err(|e| format!("{} is not JSON: {}", path.display(), e))?;
+        if json["type"].as_str() != Some("service_account") || json["client_email"].is_null() {
+            return Err(format!("{} is not a service account key", path.display()));
+        }
+        let impersonate = form.value("impersonate");
+        if !impersonate.trim().is_empty() && !impersonate.contains('@') {
+            return Err(format!("'{}' is not an email address", impersonate));
+        }
+        Ok(path.to_string_lossy().to_string())
+    }
+}
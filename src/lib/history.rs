@@ -0,0 +1,219 @@
+#![allow(dead_code)]
+
+pub mod history {
+    use std::{
+        fs::{self, OpenOptions},
+        io::Write,
+        path::{Path, PathBuf},
+    };
+
+    use serde_json::{json, Value};
+
+    use crate::lib::{
+        config::config::{get_config_path, ConfigType},
+        schedule::schedule::format_time,
+        utils::utils::*,
+    };
+
+    /**
+     * One finished job as kept in `history.jsonl`, newest last
+     */
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct HistoryEntryStruct {
+        pub id: String,
+        pub label: String,
+        pub args: Vec<String>,
+        pub state: String,
+        pub started: i64,
+        pub ended: i64,
+        pub exit: String,
+        pub bytes: u64,
+        pub files: u64,
+        pub deletes: u64,
+        pub errors: u64,
+        pub error: String,
+        pub log_path: String,
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum ReportFormat {
+        Json,
+        Markdown,
+    }
+
+    /**
+     * Folder next to rrclone.json holding the history, job logs and reports
+     */
+    pub fn rrclone_dir() -> PathBuf {
+        let path = get_config_path(ConfigType::RRclone);
+        match Path::new(&path).parent() {
+            Some(dir) => dir.to_path_buf(),
+            None => PathBuf::from("."),
+        }
+    }
+
+    pub fn history_path() -> PathBuf {
+        rrclone_dir().join("history.jsonl")
+    }
+
+    /**
+     * Where a job writes its full rclone log
+     */
+    pub fn log_path(started: i64, id: usize) -> PathBuf {
+        rrclone_dir()
+            .join("logs")
+            .join(format!("job-{}-{}.log", started, id))
+    }
+
+    impl HistoryEntryStruct {
+        pub fn to_json(&self) -> Value {
+            json!({
+                "id": self.id,
+                "label": self.label,
+                "args": self.args,
+                "state": self.state,
+                "started": self.started,
+                "ended": self.ended,
+                "exit": self.exit,
+                "bytes": self.bytes,
+                "files": self.files,
+                "deletes": self.deletes,
+                "errors": self.errors,
+                "error": self.error,
+                "log_path": self.log_path,
+            })
+        }
+
+        fn from_json(json: &Value) -> Option<HistoryEntryStruct> {
+            Some(HistoryEntryStruct {
+                id: json["id"].as_str()?.to_owned(),
+                label: json["label"].as_str().unwrap_or_default().to_owned(),
+                args: match json["args"].as_array() {
+                    Some(args) => args
+                        .iter()
+                        .filter_map(|a| a.as_str().map(String::from))
+                        .collect(),
+                    None => vec![],
+                },
+                state: json["state"].as_str().unwrap_or_default().to_owned(),
+                started: json["started"].as_i64().unwrap_or(0),
+                ended: json["ended"].as_i64().unwrap_or(0),
+                exit: json["exit"].as_str().unwrap_or_default().to_owned(),
+                bytes: json["bytes"].as_u64().unwrap_or(0),
+                files: json["files"].as_u64().unwrap_or(0),
+                deletes: json["deletes"].as_u64().unwrap_or(0),
+                errors: json["errors"].as_u64().unwrap_or(0),
+                error: json["error"].as_str().unwrap_or_default().to_owned(),
+                log_path: json["log_path"].as_str().unwrap_or_default().to_owned(),
+            })
+        }
+
+        pub fn duration(&self) -> u64 {
+            (self.ended - self.started).max(0) as u64
+        }
+
+        /**
+         * Case insensitive match on the label, the state or the start date
+         */
+        pub fn matches(&self, filter: &str) -> bool {
+            let filter = filter.to_lowercase();
+            filter.is_empty()
+                || self.label.to_lowercase().contains(&filter)
+                || self.state.contains(&filter)
+                || format_time(self.started).contains(&filter)
+        }
+
+        pub fn to_markdown(&self) -> String {
+            let mut report = format!(
+                "# {}\n\n\
+                 | | |\n|---|---|\n\
+                 | State | {} |\n\
+                 | Exit | {} |\n\
+                 | Started | {} |\n\
+                 | Ended | {} |\n\
+                 | Duration | {} |\n\
+                 | Transferred | {} |\n\
+                 | Files | {} |\n\
+                 | Deletes | {} |\n\
+                 | Errors | {} |\n\n\
+                 ```\nrclone {}\n```\n",
+                self.label,
+                self.state,
+                self.exit,
+                format_time(self.started),
+                format_time(self.ended),
+                format_duration(self.duration()),
+                format_size(self.bytes),
+                self.files,
+                self.deletes,
+                self.errors,
+                self.args.join(" "),
+            );
+            if !self.error.is_empty() {
+                report.push_str(&format!("\n## Last error\n\n```\n{}\n```\n", self.error));
+            }
+            if !self.log_path.is_empty() {
+                report.push_str(&format!("\nFull log: `{}`\n", self.log_path));
+            }
+            report
+        }
+    }
+
+    pub fn append_history(entry: &HistoryEntryStruct) {
+        let path = history_path();
+        if let Some(dir) = path.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let written = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| writeln!(file, "{}", entry.to_json()));
+        if let Err(e) = written {
+            log_error(format!("Couldnt write job history: {}", e));
+        }
+    }
+
+    /**
+     * All recorded jobs, newest first, broken lines are skipped
+     */
+    pub fn read_history() -> Vec<HistoryEntryStruct> {
+        let content = fs::read_to_string(history_path()).unwrap_or_default();
+        let mut entries: Vec<HistoryEntryStruct> = content
+            .lines()
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .filter_map(|json| HistoryEntryStruct::from_json(&json))
+            .collect();
+        entries.reverse();
+        entries
+    }
+
+    pub fn read_log(entry: &HistoryEntryStruct) -> Vec<String> {
+        match fs::read_to_string(&entry.log_path) {
+            Ok(log) => log.lines().map(String::from).collect(),
+            Err(e) => vec![format!("Couldnt read {}: {}", entry.log_path, e)],
+        }
+    }
+
+    /**
+     * Writes the report in the reports folder, returns its path
+     */
+    pub fn export_report(
+        entry: &HistoryEntryStruct,
+        format: ReportFormat,
+    ) -> Result<PathBuf, String> {
+        let dir = rrclone_dir().join("reports");
+        fs::create_dir_all(&dir).map_err(|e| e.to_string())?;
+        let (extension, content) = match format {
+            ReportFormat::Json => (
+                "json",
+                serde_json::to_string_pretty(&entry.to_json()).map_err(|e| e.to_string())?,
+            ),
+            ReportFormat::Markdown => ("md", entry.to_markdown()),
+        };
+        let path = dir.join(format!("job-{}.{}", entry.id, extension));
+        fs::write(&path, content).map_err(|e| e.to_string())?;
+        log_info(format!("Report written to {}", path.display()));
+        Ok(path)
+    }
+}
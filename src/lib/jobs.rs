@@ -0,0 +1,342 @@
+#![allow(dead_code)]
+
+pub mod jobs {
+    use std::{
+        fs::{self, File},
+        io::{BufRead, BufReader, Write},
+        process::{Child, Command, Stdio},
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    use serde_json::Value;
+
+    use crate::lib::{
+        history::history::{append_history, log_path, HistoryEntryStruct},
+        schedule::schedule::now,
+        utils::utils::*,
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum JobState {
+        Queued,
+        Running,
+        Done,
+        Failed,
+        Cancelled,
+    }
+
+    impl JobState {
+        pub fn label(&self) -> &'static str {
+            match self {
+                JobState::Queued => "queued",
+                JobState::Running => "running",
+                JobState::Done => "done",
+                JobState::Failed => "failed",
+                JobState::Cancelled => "cancelled",
+            }
+        }
+
+        pub fn finished(&self) -> bool {
+            matches!(
+                self,
+                JobState::Done | JobState::Failed | JobState::Cancelled
+            )
+        }
+    }
+
+    /**
+     * One rclone command run in the background, progress read from its JSON log
+     */
+    pub struct JobStruct {
+        pub id: usize,
+        pub label: String,
+        pub args: Vec<String>,
+        pub state: JobState,
+        pub bytes: u64,
+        pub total_bytes: u64,
+        pub speed: f64,
+        pub eta: Option<u64>,
+        pub files: u64,
+        pub deletes: u64,
+        pub errors: u64,
+        pub error: String,
+        pub started: i64,
+        pub ended: i64,
+        pub exit: String,
+        pub log_path: String,
+        process: Option<Child>,
+        rx: Option<Receiver<String>>,
+        log: Option<File>,
+    }
+
+    /**
+     * Starts queued jobs in order while fewer than `max_jobs` run
+     */
+    pub fn start_queued(jobs: &mut [JobStruct], max_jobs: usize) {
+        let mut running = jobs.iter().filter(|j| j.state == JobState::Running).count();
+        for job in jobs.iter_mut() {
+            if running >= max_jobs {
+                break;
+            }
+            if job.state == JobState::Queued {
+                job.start();
+                running += (job.state == JobState::Running) as usize;
+            }
+        }
+    }
+
+    impl JobStruct {
+        pub fn new(id: usize, label: &str, args: Vec<String>) -> JobStruct {
+            JobStruct {
+                id,
+                label: label.to_owned(),
+                args,
+                state: JobState::Queued,
+                bytes: 0,
+                total_bytes: 0,
+                speed: 0.0,
+                eta: None,
+                files: 0,
+                deletes: 0,
+                errors: 0,
+                error: String::new(),
+                started: 0,
+                ended: 0,
+                exit: String::new(),
+                log_path: String::new(),
+                process: None,
+                rx: None,
+                log: None,
+            }
+        }
+
+        /**
+         * Runs rclone with stats every second as JSON lines on stderr
+         */
+        pub fn start(&mut self) {
+            let mut args = self.args.clone();
+            args.extend([
+                String::from("--use-json-log"),
+                String::from("--stats"),
+                String::from("1s"),
+                String::from("--stats-log-level"),
+                String::from("NOTICE"),
+            ]);
+            log_info(format!("Job {} started: {}", self.id, self.label));
+            log_trace(format!("rclone {}", args.join(" ")));
+            self.started = now();
+            let path = log_path(self.started, self.id);
+            if let Some(dir) = path.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            self.log = match File::create(&path) {
+                Ok(mut log) => {
+                    let _ = writeln!(log, "rclone {}", args.join(" "));
+                    self.log_path = path.to_string_lossy().to_string();
+                    Some(log)
+                }
+                Err(e) => {
+                    log_warning(format!("Job {} has no log file: {}", self.id, e));
+                    None
+                }
+            };
+            let process = Command::new("rclone")
+                .args(&args)
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::piped())
+                .spawn();
+            match process {
+                Ok(mut process) => {
+                    let (tx, rx) = mpsc::channel::<String>();
+                    if let Some(stderr) = process.stderr.take() {
+                        thread::spawn(move || {
+                            for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                                if tx.send(line).is_err() {
+                                    break;
+                                }
+                            }
+                        });
+                    }
+                    self.process = Some(process);
+                    self.rx = Some(rx);
+                    self.state = JobState::Running;
+                }
+                Err(e) => {
+                    self.error = e.to_string();
+                    self.state = JobState::Failed;
+                    log_error(format!("Job {} couldnt start: {}", self.id, e));
+                    self.record();
+                }
+            }
+        }
+
+        fn read_line(&mut self, line: &str) {
+            if let Some(log) = self.log.as_mut() {
+                let _ = writeln!(log, "{}", line);
+            }
+            let json: Value = match serde_json::from_str(line) {
+                Ok(json) => json,
+                Err(_) => return,
+            };
+            let stats = &json["stats"];
+            if stats.is_object() {
+                self.bytes = stats["bytes"].as_u64().unwrap_or(self.bytes);
+                self.total_bytes = stats["totalBytes"].as_u64().unwrap_or(self.total_bytes);
+                self.speed = stats["speed"].as_f64().unwrap_or(0.0);
+                self.eta = stats["eta"].as_u64();
+                self.files = stats["transfers"].as_u64().unwrap_or(self.files);
+                self.deletes = stats["deletes"].as_u64().unwrap_or(self.deletes);
+                self.errors = stats["errors"].as_u64().unwrap_or(self.errors);
+            }
+            if json["level"].as_str() == Some("error") {
+                self.error = json["msg"].as_str().unwrap_or_default().trim().to_owned();
+                log_error(format!("Job {}: {}", self.id, self.error));
+            }
+        }
+
+        /**
+         * Reads new progress, returns true once the job just finished
+         */
+        pub fn poll(&mut self) -> bool {
+            if self.state != JobState::Running {
+                return false;
+            }
+            let lines: Vec<String> = match self.rx.as_ref() {
+                Some(rx) => rx.try_iter().collect(),
+                None => vec![],
+            };
+            for line in lines {
+                self.read_line(&line);
+            }
+            let status = match self.process.as_mut().map(|p| p.try_wait()) {
+                Some(Ok(Some(status))) => status,
+                Some(Ok(None)) => return false,
+                Some(Err(e)) => {
+                    self.error = e.to_string();
+                    self.state = JobState::Failed;
+                    self.record();
+                    return true;
+                }
+                None => return false,
+            };
+            if let Some(rx) = self.rx.take() {
+                for line in rx.iter() {
+                    self.read_line(&line);
+                }
+            }
+            self.process = None;
+            self.exit = status.to_string();
+            self.state = match status.success() {
+                true => JobState::Done,
+                false => JobState::Failed,
+            };
+            match self.state {
+                JobState::Done => log_info(format!("Job {} done: {}", self.id, self.label)),
+                _ => log_error(format!(
+                    "Job {} failed ({}): {}",
+                    self.id, status, self.label
+                )),
+            }
+            self.record();
+            true
+        }
+
+        /**
+         * Adds the finished job to the history with its stats and log file
+         */
+        fn record(&mut self) {
+            self.ended = now();
+            self.log = None;
+            if self.started == 0 {
+                self.started = self.ended;
+            }
+            append_history(&HistoryEntryStruct {
+                id: format!("{}-{}", self.started, self.id),
+                label: self.label.clone(),
+                args: self.args.clone(),
+                state: self.state.label().to_owned(),
+                started: self.started,
+                ended: self.ended,
+                exit: self.exit.clone(),
+                bytes: self.bytes,
+                files: self.files,
+                deletes: self.deletes,
+                errors: self.errors,
+                error: self.error.clone(),
+                log_path: self.log_path.clone(),
+            });
+        }
+
+        /**
+         * Kills a running job, a queued one just never starts
+         */
+        pub fn cancel(&mut self) {
+            if self.state.finished() {
+                return;
+            }
+            let started = self.process.is_some();
+            if let Some(mut process) = self.process.take() {
+                let _ = process.kill();
+                let _ = process.wait();
+                self.exit = String::from("killed");
+            }
+            self.rx = None;
+            self.state = JobState::Cancelled;
+            log_warning(format!("Job {} cancelled: {}", self.id, self.label));
+            if started {
+                self.record();
+            }
+        }
+
+        /**
+         * Puts a failed or cancelled job back in the queue with fresh stats
+         */
+        pub fn retry(&mut self) -> bool {
+            if !matches!(self.state, JobState::Failed | JobState::Cancelled) {
+                return false;
+            }
+            self.state = JobState::Queued;
+            self.bytes = 0;
+            self.total_bytes = 0;
+            self.speed = 0.0;
+            self.eta = None;
+            self.files = 0;
+            self.deletes = 0;
+            self.errors = 0;
+            self.error = String::new();
+            self.started = 0;
+            self.exit = String::new();
+            self.log_path = String::new();
+            log_info(format!("Job {} queued again: {}", self.id, self.label));
+            true
+        }
+
+        /**
+         * Done part of the transfer, 0 to 1
+         */
+        pub fn ratio(&self) -> f64 {
+            match (self.state, self.total_bytes) {
+                (JobState::Done, _) => 1.0,
+                (_, 0) => 0.0,
+                (_, total) => (self.bytes as f64 / total as f64).min(1.0),
+            }
+        }
+
+        pub fn progress(&self) -> String {
+            let mut progress = format!(
+                "{} / {}",
+                format_size(self.bytes),
+                format_size(self.total_bytes)
+            );
+            if self.state == JobState::Running {
+                progress.push_str(&format!(" {}/s", format_size(self.speed as u64)));
+                if let Some(eta) = self.eta {
+                    progress.push_str(&format!(" ETA {}", format_duration(eta)));
+                }
+            }
+            progress
+        }
+    }
+}
@@ -8,38 +8,120 @@ pub mod mount {
     use rand::thread_rng;
     use std::env;
     use std::fs;
+    use std::io::{BufRead, BufReader};
     use std::process::Child;
     use std::process::Command;
     use std::process::Stdio;
-    use std::sync::mpsc;
+    use std::sync::{Arc, Mutex};
     use std::thread;
 
     use crate::lib::app::app_mod::App;
-    use crate::lib::{config::config::DriveStruct, utils::utils::*};
+    use crate::lib::{
+        config::config::{DriveStruct, MountOptionsStruct},
+        filter::filter::filter_args,
+        utils::utils::*,
+    };
 
-    pub fn start_mounting(drive: &DriveStruct, app: &mut App) {
-        log_info(format!("Start mounting {}", drive.name));
-        let point: String = match env::consts::OS {
+    /**
+     * Remote control of a running mount, rclone picks the port and tells it on stderr
+     */
+    #[derive(Debug, Clone)]
+    pub struct RcStruct {
+        pub user: String,
+        pub pass: String,
+        url: Arc<Mutex<Option<String>>>,
+    }
+
+    impl RcStruct {
+        pub fn new() -> RcStruct {
+            RcStruct {
+                user: random_token(12),
+                pass: random_token(32),
+                url: Arc::new(Mutex::new(None)),
+            }
+        }
+
+        /**
+         * Address of the rc server, None until rclone reported it
+         */
+        pub fn url(&self) -> Option<String> {
+            self.url.lock().ok().and_then(|url| url.clone())
+        }
+
+        /**
+         * Credentials as environment variables, `rclone mount` and `rclone rc` read them
+         */
+        pub fn mount_envs(&self) -> [(&str, &str); 2] {
+            [
+                ("RCLONE_RC_USER", &self.user),
+                ("RCLONE_RC_PASS", &self.pass),
+            ]
+        }
+
+        pub fn client_envs(&self) -> [(&str, &str); 2] {
+            [("RCLONE_USER", &self.user), ("RCLONE_PASS", &self.pass)]
+        }
+    }
+
+    impl Default for RcStruct {
+        fn default() -> RcStruct {
+            RcStruct::new()
+        }
+    }
+
+    /**
+     * `... NOTICE: Serving remote control on http://127.0.0.1:41234/`
+     */
+    pub fn parse_rc_url(line: &str) -> Option<String> {
+        let (_, rest) = line.split_once("Serving remote control on ")?;
+        let url = rest.split_whitespace().next()?;
+        url.starts_with("http").then(|| url.to_owned())
+    }
+
+    pub fn mount_flags(options: &MountOptionsStruct) -> Vec<String> {
+        let mut flags = vec![
+            String::from("--vfs-cache-mode"),
+            options.vfs_cache_mode.clone(),
+        ];
+        if options.allow_other {
+            flags.push(String::from("--allow-other"));
+        }
+        if options.read_only {
+            flags.push(String::from("--read-only"));
+        }
+        if !options.bwlimit.is_empty() {
+            flags.push(String::from("--bwlimit"));
+            flags.push(options.bwlimit.clone());
+        }
+        // flags.push(String::from("--vfs-read-chunk-size"));
+        // flags.push(String::from("32M"));
+        flags.extend(filter_args(&options.filter));
+        flags.extend(options.extra_flags.iter().cloned());
+        flags
+    }
+
+    pub fn get_mount_point(drive: &DriveStruct, options: &MountOptionsStruct) -> String {
+        if !options.mount_point.is_empty() {
+            return options.mount_point.clone();
+        }
+        match env::consts::OS {
             "windows" => {
                 let mounted_points: Vec<char> = mountpaths()
                     .unwrap()
                     .iter()
                     .map(|f| {
-                        let point = f
+                        let point = *f
                             .to_str()
                             .unwrap()
-                            .clone()
-                            .split(":")
+                            .split(':')
                             .collect::<Vec<_>>()
                             .first()
-                            .unwrap()
-                            .clone();
+                            .unwrap();
                         point.to_owned().chars().next().unwrap()
                     })
                     .collect();
-                let alphabet: Vec<char> = ('A'..='Z').into_iter().collect::<Vec<char>>();
+                let alphabet: Vec<char> = ('A'..='Z').collect::<Vec<char>>();
                 let mut difference: Vec<char> = alphabet
-                    .clone()
                     .into_iter()
                     .filter(|&item| !mounted_points.contains(&item))
                     .collect::<Vec<char>>();
@@ -50,69 +132,137 @@ pub mod mount {
                 let mut path = std::env::var("HOME").expect("Can not get HOME directory");
                 let name = format!("/{}", drive.name.clone());
                 path.push_str(&name);
-                log_debug(path.clone());
-                match fs::create_dir(&path) {
-                    Ok(_) => {
-                        log_info(format!("Already existing directory on {}", path));
-                    }
-                    Err(_) => {
-                        // fs::create_dir(&path).expect("Could not create directory");
-                        log_info(format!("Created empty directory for drive on {}", path));
-                    }
-                }
                 path
             }
-        };
+        }
+    }
 
-        let (tx, rx) = mpsc::channel::<String>();
-        let (tx1, rx1) = mpsc::channel::<Child>();
-        tx.send(drive.name.clone()).unwrap();
-        thread::spawn(move || {
-            let child = Command::new("rclone")
-                .args(&[
-                    String::from("mount"),
-                    format!("{}:", rx.recv().unwrap()),
-                    format!("{}", point),
-                    String::from("--vfs-cache-mode"),
-                    String::from("full"),
-                    String::from("--allow-other"),
-                    // String::from("--vfs-read-chunk-size"),
-                    // String::from("32M"),
-                ])
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn();
-            match child {
-                Ok(child) => {
-                    // let child_stdout = child.stdout.take().unwrap();
-                    // let mut stdout_lines = BufReader::new(child_stdout);
-                    // for line in stdout_lines {
-                    //     let line = (&line.unwrap()[20..]).to_string();
-                    //     println!("{}", line);
-                    //     log_info(format!("{}", line));
-                    // }
-                    // let mut line = String::with_capacity(10);
-                    // println!("Line : {}", line.clone());
-                    // while stdout_lines.read_line(&mut line).unwrap_or(0) > 0 {
-                    //     log_info(format!("{}", line));
-                    //     line.clear();
-                    // }
-                    // let child_stderr = child.stdout.take().unwrap();
-                    // let stderr_lines = BufReader::new(child_stderr).lines();
-                    // for line in stderr_lines {
-                    //     let line = (&line.unwrap()[20..]).to_string();
-                    //     log_error(format!("{}", line));
-                    // }
-                    tx1.send(child).unwrap();
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum MountState {
+        Unmounted,
+        Mounting,
+        Mounted,
+        Failed,
+        Serving,
+    }
+
+    impl MountState {
+        pub fn label(&self) -> &'static str {
+            match self {
+                MountState::Unmounted => "unmounted",
+                MountState::Mounting => "mounting",
+                MountState::Mounted => "mounted",
+                MountState::Failed => "failed",
+                MountState::Serving => "serving",
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct MountStatusStruct {
+        pub state: MountState,
+        pub point: String,
+    }
+
+    /**
+     * Mount points the OS currently knows, without trailing separators
+     */
+    pub fn active_mount_points() -> Vec<String> {
+        match mountpaths() {
+            Ok(paths) => paths
+                .iter()
+                .map(|p| trim_point(&p.to_string_lossy()).to_owned())
+                .collect(),
+            Err(e) => {
+                log_error(format!("Couldnt list mount points: {:?}", e));
+                vec![]
+            }
+        }
+    }
+
+    fn trim_point(point: &str) -> &str {
+        match point.trim_end_matches(['/', '\\']) {
+            "" => point,
+            // windows drive letters come back as `X:\`
+            trimmed => trimmed.trim_end_matches(':'),
+        }
+    }
+
+    pub fn is_point_active(active: &[String], point: &str) -> bool {
+        active.iter().any(|p| p == trim_point(point))
+    }
+
+    /**
+     * Starts `rclone mount`, returns false if rclone couldnt be started
+     */
+    pub fn start_mounting(drive: &DriveStruct, app: &mut App) -> bool {
+        log_info(format!("Start mounting {}", drive.name));
+        let options = app.settings.mount_options(&drive.name);
+        let point = get_mount_point(drive, &options);
+        if env::consts::OS != "windows" {
+            log_debug(point.clone());
+            match fs::create_dir(&point) {
+                Ok(_) => {
+                    log_info(format!("Created empty directory for drive on {}", point));
+                }
+                Err(_) => {
+                    log_info(format!("Already existing directory on {}", point));
                 }
-                Err(e) => log_error(e.to_string()),
             }
-        });
-        // handle.join().unwrap();
-        app.processes_mounted.push(rx1.recv().unwrap());
+        }
+
+        let mut args = vec![
+            String::from("mount"),
+            format!("{}:", drive.name),
+            point.clone(),
+        ];
+        args.extend(mount_flags(&options));
+        // remote control, so limits can change while mounted, port 0 lets rclone pick one
+        let rc = RcStruct::new();
+        args.push(String::from("--rc"));
+        args.push(String::from("--rc-addr"));
+        args.push(String::from("127.0.0.1:0"));
+        let child = Command::new("rclone")
+            .args(&args)
+            .envs(rc.mount_envs())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn();
+        match child {
+            Ok(mut child) => {
+                if let Some(stderr) = child.stderr.take() {
+                    let url = rc.url.clone();
+                    let name = drive.name.clone();
+                    thread::spawn(move || {
+                        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                            match parse_rc_url(&line) {
+                                Some(found) => {
+                                    if let Ok(mut url) = url.lock() {
+                                        *url = Some(found);
+                                    }
+                                }
+                                None if line.contains("ERROR") => {
+                                    log_error(format!("{}: {}", name, line))
+                                }
+                                None => log_debug(format!("{}: {}", name, line)),
+                            }
+                        }
+                    });
+                }
+                app.drives_mounted.push(drive.clone());
+                app.processes_mounted.push(child);
+                app.rc_mounted.push(rc);
+                app.points_mounted.push(point);
+                true
+            }
+            Err(e) => {
+                log_error(e.to_string());
+                false
+            }
+        }
     }
 
-    pub fn stop_mounting(drive: &DriveStruct, process: &mut Child) {
+    pub fn stop_mounting(drive: &DriveStruct, point: &str, mut process: Child) {
         log_warning(format!(
             "Stop mounting {}\nSave your stuff {}",
             drive.name,
@@ -122,31 +272,35 @@ pub mod mount {
             "windows" => process.kill().expect("command wasn't running"),
             "linux" => {
                 match Command::new("fusermount")
-                    .args(&[String::from("-uz"), format!("{}", drive.name)])
+                    .args(["-uz", point])
                     .stdout(Stdio::piped())
                     .stderr(Stdio::piped())
                     .spawn()
                 {
-                    Ok(_) => log_debug("Unmounted successfully".to_owned()),
+                    Ok(child) => {
+                        log_debug("Unmounted successfully".to_owned());
+                        thread::spawn(move || child.wait_with_output());
+                    }
                     Err(e) => log_error(e.to_string()),
                 }
             }
             "macos" => {
                 match Command::new("diskutil")
-                    .args(&[
-                        String::from("unmount"),
-                        format!("/Users/evildave/{}", drive.name),
-                    ])
+                    .args(["unmount", point])
                     .stdout(Stdio::piped())
                     .stderr(Stdio::piped())
                     .spawn()
                 {
-                    Ok(_) => log_debug("Unmounted successfully".to_owned()),
+                    Ok(child) => {
+                        log_debug("Unmounted successfully".to_owned());
+                        thread::spawn(move || child.wait_with_output());
+                    }
                     Err(e) => log_error(e.to_string()),
                 }
             }
             _ => panic!("Platform not supported"),
         };
         log_debug(format!("Killed process ID: {}", process.id()));
+        thread::spawn(move || process.wait());
     }
 }
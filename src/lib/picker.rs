@@ -0,0 +1,67 @@
+#![allow(dead_code)]
+
+pub mod picker {
+    use std::{
+        env, fs,
+        path::{Path, PathBuf},
+    };
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct EntryStruct {
+        pub name: String,
+        pub path: PathBuf,
+        pub is_dir: bool,
+    }
+
+    /**
+     * Expands a leading `~` to the home folder
+     */
+    pub fn expand_home(path: &str) -> PathBuf {
+        match (path.strip_prefix('~'), env::var("HOME")) {
+            (Some(rest), Ok(home)) => PathBuf::from(format!("{}{}", home, rest)),
+            _ => PathBuf::from(path),
+        }
+    }
+
+    /**
+     * Folder to open the picker in, the one of `current` if it exists
+     */
+    pub fn start_dir(current: &str) -> PathBuf {
+        let current = expand_home(current.trim());
+        match current.parent() {
+            Some(parent) if !current.as_os_str().is_empty() && parent.is_dir() => {
+                parent.to_path_buf()
+            }
+            _ => PathBuf::from(env::var("HOME").unwrap_or(String::from("/"))),
+        }
+    }
+
+    /**
+     * `..`, then folders, then files with the extension, hidden ones skipped
+     */
+    pub fn list_dir(dir: &Path, extension: &str) -> Result<Vec<EntryStruct>, String> {
+        let mut entries: Vec<EntryStruct> = fs::read_dir(dir)
+            .map_err(|e| format!("{}: {}", dir.display(), e))?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| EntryStruct {
+                name: entry.file_name().to_string_lossy().to_string(),
+                is_dir: entry.path().is_dir(),
+                path: entry.path(),
+            })
+            .filter(|e| !e.name.starts_with('.'))
+            .filter(|e| e.is_dir || e.name.ends_with(extension))
+            .collect();
+        entries.sort_by_key(|e| (!e.is_dir, e.name.to_lowercase()));
+        if let Some(parent) = dir.parent() {
+            entries.insert(
+                0,
+                EntryStruct {
+                    name: String::from(".."),
+                    path: parent.to_path_buf(),
+                    is_dir: true,
+                },
+            );
+        }
+        Ok(entries)
+    }
+}
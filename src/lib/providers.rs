@@ -0,0 +1,301 @@
+#![allow(dead_code)]
+
+pub mod providers {
+    use serde_json::Value;
+
+    use crate::lib::{
+        form::form::*,
+        utils::utils::{parse_size, run_rclone},
+    };
+
+    /**
+     * Backends shown first in the wizard, the rest follow by name
+     */
+    const PREFERRED: [&str; 10] = [
+        "drive", "onedrive", "dropbox", "s3", "b2", "sftp", "webdav", "box", "pcloud", "local",
+    ];
+
+    pub const NAME_KEY: &str = ":name";
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ProviderOptionStruct {
+        pub name: String,
+        pub help: String,
+        pub default: String,
+        pub option_type: String,
+        pub provider: String,
+        pub required: bool,
+        pub is_password: bool,
+        pub advanced: bool,
+        pub exclusive: bool,
+        pub examples: Vec<(String, String)>,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct ProviderStruct {
+        pub name: String,
+        pub description: String,
+        pub options: Vec<ProviderOptionStruct>,
+    }
+
+    fn parse_option(option: &Value) -> ProviderOptionStruct {
+        let default = match option["DefaultStr"].as_str() {
+            Some(default) => default.to_owned(),
+            None => match &option["Default"] {
+                Value::String(default) => default.clone(),
+                Value::Null => String::new(),
+                default => default.to_string(),
+            },
+        };
+        ProviderOptionStruct {
+            name: option["Name"].as_str().unwrap_or_default().to_owned(),
+            help: option["Help"].as_str().unwrap_or_default().to_owned(),
+            default,
+            option_type: option["Type"].as_str().unwrap_or("string").to_owned(),
+            provider: option["Provider"].as_str().unwrap_or_default().to_owned(),
+            required: option["Required"].as_bool().unwrap_or(false),
+            is_password: option["IsPassword"].as_bool().unwrap_or(false),
+            advanced: option["Advanced"].as_bool().unwrap_or(false)
+                || option["Hide"].as_i64().unwrap_or(0) != 0,
+            exclusive: option["Exclusive"].as_bool().unwrap_or(false),
+            examples: match option["Examples"].as_array() {
+                Some(examples) => examples
+                    .iter()
+                    .map(|e| {
+                        (
+                            e["Value"].as_str().unwrap_or_default().to_owned(),
+                            e["Provider"].as_str().unwrap_or_default().to_owned(),
+                        )
+                    })
+                    .collect(),
+                None => vec![],
+            },
+        }
+    }
+
+    /**
+     * Every backend rclone knows, from `rclone config providers`
+     */
+    pub fn read_providers() -> Result<Vec<ProviderStruct>, String> {
+        let output = run_rclone(&[String::from("config"), String::from("providers")])?;
+        let json: Value = serde_json::from_str(&output).map_err(|e| e.to_string())?;
+        let mut providers: Vec<ProviderStruct> = json
+            .as_array()
+            .ok_or("unexpected rclone config providers output")?
+            .iter()
+            .map(|p| ProviderStruct {
+                name: p["Prefix"]
+                    .as_str()
+                    .or(p["Name"].as_str())
+                    .unwrap_or_default()
+                    .to_owned(),
+                description: p["Description"].as_str().unwrap_or_default().to_owned(),
+                options: match p["Options"].as_array() {
+                    Some(options) => options.iter().map(parse_option).collect(),
+                    None => vec![],
+                },
+            })
+            .filter(|p| !p.name.is_empty())
+            .collect();
+        providers.sort_by_key(|p| {
+            (
+                PREFERRED
+                    .iter()
+                    .position(|n| *n == p.name)
+                    .unwrap_or(PREFERRED.len()),
+                p.name.clone(),
+            )
+        });
+        Ok(providers)
+    }
+
+    /**
+     * rclone conditions look like `AWS,Ceph` or `!AWS,Minio`, empty matches all
+     */
+    fn provider_matches(condition: &str, provider: &str) -> bool {
+        if condition.is_empty() {
+            return true;
+        }
+        let (negate, list) = match condition.strip_prefix('!') {
+            Some(list) => (true, list),
+            None => (false, condition),
+        };
+        list.split(',').any(|p| p.trim() == provider) != negate
+    }
+
+    fn visible_options<'a>(
+        provider: &'a ProviderStruct,
+        sub_provider: &'a str,
+    ) -> impl Iterator<Item = &'a ProviderOptionStruct> {
+        provider
+            .options
+            .iter()
+            .filter(move |o| !o.advanced && provider_matches(&o.provider, sub_provider))
+    }
+
+    /**
+     * Basic options of a backend as a form, keeping values from a previous form
+     */
+    pub fn provider_form(provider: &ProviderStruct, previous: Option<&FormStruct>) -> FormStruct {
+        let previous_value = |key: &str, default: &str| match previous {
+            Some(form) if form.fields.iter().any(|f| f.key == key) => form.value(key),
+            _ => default.to_owned(),
+        };
+        let sub_provider = previous_value("provider", "");
+
+        let mut fields = vec![FieldStruct::new(
+            NAME_KEY,
+            "Remote name",
+            &previous_value(NAME_KEY, ""),
+            FieldKind::Text,
+        )
+        .with_help("Name of the new remote")];
+
+        for option in visible_options(provider, &sub_provider) {
+            let examples: Vec<String> = option
+                .examples
+                .iter()
+                .filter(|(_, p)| provider_matches(p, &sub_provider))
+                .map(|(value, _)| value.clone())
+                .collect();
+            let kind = if option.is_password {
+                FieldKind::Secret
+            } else if option.option_type == "bool" {
+                FieldKind::Toggle
+            } else if option.exclusive && !examples.is_empty() {
+                FieldKind::Choice(examples.clone())
+            } else {
+                FieldKind::Text
+            };
+            let mut help = option.help.lines().next().unwrap_or_default().to_owned();
+            if kind == FieldKind::Text && !examples.is_empty() {
+                help.push_str(&format!(" e.g. {}", examples.join(", ")));
+            }
+            let mut value = previous_value(&option.name, &option.default);
+            if let FieldKind::Choice(choices) = &kind {
+                if !choices.contains(&value) {
+                    value = choices[0].clone();
+                }
+            }
+            let label = match option.required {
+                true => format!("{} *", option.name),
+                false => option.name.clone(),
+            };
+            fields.push(FieldStruct::new(&option.name, &label, &value, kind).with_help(&help));
+        }
+
+        let mut form = FormStruct::with_fields(fields);
+        match previous {
+            Some(previous) => form.selected = previous.selected.min(form.fields.len() - 1),
+            // options depend on the sub provider picked by default, like AWS for s3
+            None if form.fields.iter().any(|f| f.key == "provider") => {
+                return provider_form(provider, Some(&form))
+            }
+            None => {}
+        }
+        form
+    }
+
+    pub fn validate_provider_form(
+        provider: &ProviderStruct,
+        form: &FormStruct,
+    ) -> Result<(), String> {
+        let sub_provider = form.value("provider");
+        for option in visible_options(provider, &sub_provider) {
+            let value = form.value(&option.name);
+            if option.required && value.trim().is_empty() {
+                return Err(format!("{} is required", option.name));
+            }
+            if value.is_empty() {
+                continue;
+            }
+            let valid = match option.option_type.as_str() {
+                "int" => value.parse::<i64>().is_ok(),
+                "SizeSuffix" => value == "off" || parse_size(&value).is_some(),
+                _ => true,
+            };
+            if !valid {
+                return Err(format!("{} must be a {}", option.name, option.option_type));
+            }
+        }
+        Ok(())
+    }
+
+    /**
+     * `key value` pairs for `rclone config create`, skipping untouched defaults
+     */
+    pub fn provider_params(provider: &ProviderStruct, form: &FormStruct) -> Vec<String> {
+        let sub_provider = form.value("provider");
+        let mut params: Vec<String> = vec![];
+        for option in visible_options(provider, &sub_provider) {
+            let value = form.value(&option.name);
+            if value != option.default || option.required {
+                params.push(option.name.clone());
+                params.push(value);
+            }
+        }
+        params
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn option(
+            name: &str,
+            default: &str,
+            provider: &str,
+            required: bool,
+        ) -> ProviderOptionStruct {
+            ProviderOptionStruct {
+                name: name.to_owned(),
+                help: String::new(),
+                default: default.to_owned(),
+                option_type: String::from("string"),
+                provider: provider.to_owned(),
+                required,
+                is_password: false,
+                advanced: false,
+                exclusive: false,
+                examples: vec![],
+            }
+        }
+
+        #[test]
+        fn matches_provider_conditions() {
+            assert!(provider_matches("", "AWS"));
+            assert!(provider_matches("AWS,Ceph", "Ceph"));
+            assert!(!provider_matches("AWS,Ceph", "Minio"));
+            assert!(provider_matches("!AWS,Minio", "Ceph"));
+            assert!(!provider_matches("!AWS,Minio", "AWS"));
+        }
+
+        #[test]
+        fn params_skip_untouched_defaults() {
+            let provider = ProviderStruct {
+                name: String::from("s3"),
+                description: String::new(),
+                options: vec![
+                    option("provider", "AWS", "", true),
+                    option("region", "us-east-1", "AWS", false),
+                    option("endpoint", "", "!AWS", false),
+                    option("acl", "private", "", false),
+                ],
+            };
+            let mut form = provider_form(&provider, None);
+            form.set("region", "eu-west-1");
+            assert_eq!(
+                provider_params(&provider, &form),
+                vec!["provider", "AWS", "region", "eu-west-1"]
+            );
+            form.set("provider", "Minio");
+            // the app rebuilds the form when the sub provider changes
+            let mut form = provider_form(&provider, Some(&form));
+            form.set("endpoint", "http://minio:9000");
+            assert_eq!(
+                provider_params(&provider, &form),
+                vec!["provider", "Minio", "endpoint", "http://minio:9000"]
+            );
+        }
+    }
+}
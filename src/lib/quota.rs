@@ -0,0 +1,248 @@
+#![allow(dead_code)]
+
+pub mod quota {
+    use std::{
+        collections::HashMap,
+        fs,
+        path::PathBuf,
+        process::{Command, Stdio},
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    use serde_json::{json, Map, Value};
+
+    use crate::lib::{history::history::rrclone_dir, schedule::schedule::now, utils::utils::*};
+
+    /**
+     * Seconds a fetched quota is trusted before `rclone about` runs again
+     */
+    pub const QUOTA_TTL: i64 = 600;
+
+    /**
+     * What `rclone about --json` reports, backends leave out what they dont know
+     */
+    #[derive(Debug, Clone, Default, PartialEq)]
+    pub struct QuotaStruct {
+        pub total: Option<u64>,
+        pub used: Option<u64>,
+        pub trashed: Option<u64>,
+        pub other: Option<u64>,
+        pub free: Option<u64>,
+    }
+
+    impl QuotaStruct {
+        pub fn from_json(json: &Value) -> QuotaStruct {
+            QuotaStruct {
+                total: json["total"].as_u64(),
+                used: json["used"].as_u64(),
+                trashed: json["trashed"].as_u64(),
+                other: json["other"].as_u64(),
+                free: json["free"].as_u64(),
+            }
+        }
+
+        pub fn to_json(&self) -> Value {
+            json!({
+                "total": self.total,
+                "used": self.used,
+                "trashed": self.trashed,
+                "other": self.other,
+                "free": self.free,
+            })
+        }
+
+        /**
+         * Share of the total that is taken, trash and other files included
+         */
+        pub fn used_ratio(&self) -> Option<f64> {
+            let total = self.total.filter(|t| *t > 0)?;
+            let taken = match self.free {
+                Some(free) => total.saturating_sub(free),
+                None => self.used? + self.trashed.unwrap_or(0) + self.other.unwrap_or(0),
+            };
+            Some((taken as f64 / total as f64).min(1.0))
+        }
+
+        pub fn percent(&self) -> Option<u8> {
+            self.used_ratio().map(|r| (r * 100.0).round() as u8)
+        }
+
+        /**
+         * True once the remote is filled past the warning threshold
+         */
+        pub fn is_full(&self, warn_percent: u8) -> bool {
+            self.percent().is_some_and(|p| p >= warn_percent)
+        }
+    }
+
+    /**
+     * Last answer for a remote, an error when the backend has no quota
+     */
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct QuotaEntryStruct {
+        pub quota: Result<QuotaStruct, String>,
+        pub fetched: i64,
+    }
+
+    /**
+     * Quotas by remote, kept in `quota.json` so warnings show before anything runs
+     */
+    #[derive(Default)]
+    pub struct QuotaCacheStruct {
+        pub entries: HashMap<String, QuotaEntryStruct>,
+        fetching: HashMap<String, Receiver<Result<QuotaStruct, String>>>,
+    }
+
+    pub fn quota_path() -> PathBuf {
+        rrclone_dir().join("quota.json")
+    }
+
+    /**
+     * Runs `rclone about` in the background, the answer arrives on the receiver
+     */
+    pub fn fetch_quota(name: &str) -> Receiver<Result<QuotaStruct, String>> {
+        let (tx, rx) = mpsc::channel();
+        let target = format!("{}:", name);
+        log_trace(format!("rclone about --json {}", target));
+        thread::spawn(move || {
+            let output = Command::new("rclone")
+                .args(["about", "--json", &target])
+                .stdin(Stdio::null())
+                .output();
+            let quota = match output {
+                Ok(output) if output.status.success() => {
+                    serde_json::from_slice::<Value>(&output.stdout)
+                        .map(|json| QuotaStruct::from_json(&json))
+                        .map_err(|e| e.to_string())
+                }
+                Ok(output) => {
+                    let stderr = String::from_utf8_lossy(&output.stderr);
+                    Err(match stderr.lines().last() {
+                        Some(line) if line.contains("doesn't support about") => {
+                            String::from("not supported by this backend")
+                        }
+                        Some(line) => line.to_owned(),
+                        None => format!("rclone exited with {}", output.status),
+                    })
+                }
+                Err(e) => Err(e.to_string()),
+            };
+            let _ = tx.send(quota);
+        });
+        rx
+    }
+
+    impl QuotaCacheStruct {
+        /**
+         * Reads the cache written by the last session, a missing file is an empty cache
+         */
+        pub fn load() -> QuotaCacheStruct {
+            let json: Value = fs::read_to_string(quota_path())
+                .ok()
+                .and_then(|content| serde_json::from_str(&content).ok())
+                .unwrap_or_default();
+            let entries = match json.as_object() {
+                Some(remotes) => remotes
+                    .iter()
+                    .map(|(name, entry)| {
+                        let quota = match entry["error"].as_str() {
+                            Some(error) => Err(error.to_owned()),
+                            None => Ok(QuotaStruct::from_json(&entry["quota"])),
+                        };
+                        let fetched = entry["fetched"].as_i64().unwrap_or(0);
+                        (name.clone(), QuotaEntryStruct { quota, fetched })
+                    })
+                    .collect(),
+                None => HashMap::new(),
+            };
+            QuotaCacheStruct {
+                entries,
+                fetching: HashMap::new(),
+            }
+        }
+
+        pub fn save(&self) {
+            let mut remotes = Map::new();
+            for (name, entry) in self.entries.iter() {
+                let value = match &entry.quota {
+                    Ok(quota) => json!({ "quota": quota.to_json(), "fetched": entry.fetched }),
+                    Err(e) => json!({ "error": e, "fetched": entry.fetched }),
+                };
+                remotes.insert(name.clone(), value);
+            }
+            let path = quota_path();
+            if let Some(dir) = path.parent() {
+                let _ = fs::create_dir_all(dir);
+            }
+            let content = serde_json::to_string_pretty(&Value::Object(remotes))
+                .expect("couldnt serialize quotas");
+            if let Err(e) = fs::write(&path, content) {
+                log_error(format!("Couldnt write the quota cache: {}", e));
+            }
+        }
+
+        pub fn get(&self, name: &str) -> Option<&QuotaEntryStruct> {
+            self.entries.get(name)
+        }
+
+        /**
+         * The quota when known, for badges and warnings
+         */
+        pub fn quota(&self, name: &str) -> Option<&QuotaStruct> {
+            self.get(name).and_then(|e| e.quota.as_ref().ok())
+        }
+
+        pub fn is_fetching(&self, name: &str) -> bool {
+            self.fetching.contains_key(name)
+        }
+
+        /**
+         * Asks rclone again when the cached quota is older than `QUOTA_TTL` or `force` is set
+         */
+        pub fn request(&mut self, name: &str, force: bool) {
+            if self.is_fetching(name) {
+                return;
+            }
+            let fresh = self
+                .get(name)
+                .is_some_and(|e| now() - e.fetched < QUOTA_TTL);
+            if fresh && !force {
+                return;
+            }
+            self.fetching.insert(name.to_owned(), fetch_quota(name));
+        }
+
+        /**
+         * Stores the answers that arrived, returns the remotes that got one
+         */
+        pub fn poll(&mut self) -> Vec<String> {
+            let mut arrived = vec![];
+            for (name, rx) in self.fetching.iter() {
+                let quota = match rx.try_recv() {
+                    Ok(quota) => quota,
+                    Err(mpsc::TryRecvError::Empty) => continue,
+                    Err(mpsc::TryRecvError::Disconnected) => Err(String::from("rclone about died")),
+                };
+                if let Err(e) = &quota {
+                    log_warning(format!("No quota for {}: {}", name, e));
+                }
+                self.entries.insert(
+                    name.clone(),
+                    QuotaEntryStruct {
+                        quota,
+                        fetched: now(),
+                    },
+                );
+                arrived.push(name.clone());
+            }
+            for name in arrived.iter() {
+                self.fetching.remove(name);
+            }
+            if !arrived.is_empty() {
+                self.save();
+            }
+            arrived
+        }
+    }
+}
@@ -0,0 +1,237 @@
+#![allow(dead_code)]
+
+pub mod remote {
+    use crate::lib::{
+        config::config::{is_secret, read_rclone_config, ConfigStruct, DriveStruct},
+        form::form::*,
+        utils::utils::run_rclone,
+    };
+
+    pub const ADD_KEY: &str = ":add";
+    pub const NEW_NAME_KEY: &str = ":new_name";
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum RemoteAction {
+        Rename,
+        Duplicate,
+    }
+
+    /**
+     * Every key of a remote but its type, secrets masked
+     */
+    pub fn edit_form(drive: &DriveStruct) -> FormStruct {
+        let mut fields: Vec<FieldStruct> = drive
+            .options
+            .iter()
+            .filter(|(key, _)| key != "type")
+            .map(|(key, value)| {
+                let kind = match is_secret(key) {
+                    true => FieldKind::Secret,
+                    false => FieldKind::Text,
+                };
+                FieldStruct::new(key, key, value, kind)
+            })
+            .collect();
+        fields.push(
+            FieldStruct::new(ADD_KEY, "+ new option", "", FieldKind::Text)
+                .with_help("Adds an option written as key=value"),
+        );
+        FormStruct::with_fields(fields)
+    }
+
+    /**
+     * `key value` pairs that differ from the remote, plus the added option,
+     * keys the remote doesnt have yet only count once they were edited
+     */
+    pub fn changed_options(drive: &DriveStruct, form: &FormStruct) -> Result<Vec<String>, String> {
+        let mut params: Vec<String> = vec![];
+        for field in form.fields.iter().filter(|f| f.key != ADD_KEY) {
+            let changed = match drive.option(&field.key) {
+                Some(value) => value != field.value,
+                None => field.edited && !field.value.is_empty(),
+            };
+            if changed {
+                params.push(field.key.clone());
+                params.push(field.value.clone());
+            }
+        }
+        let added = form.value(ADD_KEY);
+        if !added.trim().is_empty() {
+            match added.split_once('=') {
+                Some((key, value)) if !key.trim().is_empty() && key.trim() != "type" => {
+                    params.push(key.trim().to_owned());
+                    params.push(value.trim().to_owned());
+                }
+                _ => return Err(format!("'{}' is not key=value", added)),
+            }
+        }
+        Ok(params)
+    }
+
+    /**
+     * `rclone config update`, new plain passwords get obscured by rclone
+     */
+    pub fn update_remote(drive: &DriveStruct, params: Vec<String>) -> Result<(), String> {
+        let mut args = vec![
+            String::from("config"),
+            String::from("update"),
+            drive.name.clone(),
+        ];
+        args.extend(params);
+        if drive.token.is_some() {
+            // keep the stored token instead of logging in again
+            args.push(String::from("config_refresh_token"));
+            args.push(String::from("false"));
+        }
+        args.push(String::from("--non-interactive"));
+        run_rclone(&args).map(|_| ())
+    }
+
+    /**
+     * Single field form asking for the name a remote is renamed or copied to
+     */
+    pub fn name_form(action: RemoteAction, value: &str) -> FormStruct {
+        let help = match action {
+            RemoteAction::Rename => "Remotes using this one are updated too",
+            RemoteAction::Duplicate => "Copies every option, tokens included",
+        };
+        FormStruct::with_fields(vec![FieldStruct::new(
+            NEW_NAME_KEY,
+            "New name",
+            value,
+            FieldKind::Text,
+        )
+        .with_help(help)])
+    }
+
+    pub fn delete_remote(name: &str) -> Result<(), String> {
+        run_rclone(&[
+            String::from("config"),
+            String::from("delete"),
+            name.to_owned(),
+        ])
+        .map(|_| ())
+    }
+
+    /**
+     * `rclone config create` or `update` with stored values, they are already obscured
+     * and a stored token is kept instead of logging in again
+     */
+    fn write_options(
+        command: &str,
+        name: &str,
+        drive: &DriveStruct,
+        options: &[(String, String)],
+    ) -> Result<(), String> {
+        let mut args = vec![String::from("config"), command.to_owned(), name.to_owned()];
+        if command == "create" {
+            args.push(drive.drive_type.clone());
+        }
+        for (key, value) in options.iter().filter(|(key, _)| key != "type") {
+            args.push(key.clone());
+            args.push(value.clone());
+        }
+        if drive.token.is_some() {
+            args.push(String::from("config_refresh_token"));
+            args.push(String::from("false"));
+        }
+        args.push(String::from("--no-obscure"));
+        args.push(String::from("--non-interactive"));
+        run_rclone(&args).map(|_| ())
+    }
+
+    fn find_remote(conf: &ConfigStruct, name: &str) -> Result<DriveStruct, String> {
+        conf.drives
+            .iter()
+            .find(|d| d.name == name)
+            .cloned()
+            .ok_or(format!("remote {} not found", name))
+    }
+
+    fn config_rename(name: &str, new_name: &str) -> Result<(), String> {
+        run_rclone(&[
+            String::from("config"),
+            String::from("rename"),
+            name.to_owned(),
+            new_name.to_owned(),
+        ])
+        .map(|_| ())
+    }
+
+    /**
+     * `rclone config rename`, then remotes pointing at it are updated, when one
+     * of them fails everything done so far is undone
+     */
+    pub fn rename_remote(name: &str, new_name: &str) -> Result<(), String> {
+        let conf = read_rclone_config();
+        conf.validate_name(new_name)?;
+        find_remote(&conf, name)?;
+        config_rename(name, new_name)?;
+        let mut renamed = conf.clone();
+        renamed.rename(name, new_name);
+        // dependents already updated, with the values they had before
+        let mut updated: Vec<(&DriveStruct, Vec<(String, String)>)> = vec![];
+        for (old, new) in conf.drives.iter().zip(renamed.drives.iter()) {
+            if old.name == name {
+                continue;
+            }
+            let changed: Vec<(String, String)> = old
+                .options
+                .iter()
+                .zip(new.options.iter())
+                .filter(|(before, after)| before != after)
+                .map(|(_, after)| after.clone())
+                .collect();
+            if changed.is_empty() {
+                continue;
+            }
+            let before: Vec<(String, String)> = old
+                .options
+                .iter()
+                .filter(|(key, _)| changed.iter().any(|(changed, _)| changed == key))
+                .cloned()
+                .collect();
+            if let Err(e) = write_options("update", &old.name, old, &changed) {
+                let problem = format!("couldnt point {} at {}: {}", old.name, new_name, e);
+                return Err(undo_rename(name, new_name, &updated, problem));
+            }
+            updated.push((old, before));
+        }
+        Ok(())
+    }
+
+    /**
+     * Renames the remote back and restores the updated dependents, the error
+     * says exactly what is left when that fails too
+     */
+    fn undo_rename(
+        name: &str,
+        new_name: &str,
+        updated: &[(&DriveStruct, Vec<(String, String)>)],
+        problem: String,
+    ) -> String {
+        let mut left = vec![];
+        if let Err(e) = config_rename(new_name, name) {
+            left.push(format!("{} is still called {} ({})", name, new_name, e));
+        }
+        for (drive, before) in updated.iter() {
+            if let Err(e) = write_options("update", &drive.name, drive, before) {
+                left.push(format!(
+                    "{} still points at {} ({})",
+                    drive.name, new_name, e
+                ));
+            }
+        }
+        match left.is_empty() {
+            true => format!("{}, the rename was undone", problem),
+            false => format!("{}, undoing it failed: {}", problem, left.join(", ")),
+        }
+    }
+
+    pub fn duplicate_remote(name: &str, new_name: &str) -> Result<(), String> {
+        let conf = read_rclone_config();
+        conf.validate_name(new_name)?;
+        let drive = find_remote(&conf, name)?;
+        write_options("create", new_name, &drive, &drive.options)
+    }
+}
@@ -0,0 +1,455 @@
+#![allow(dead_code)]
+
+pub mod schedule {
+    use std::{fs, path::Path};
+
+    use chrono::{Datelike, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Timelike};
+
+    use crate::lib::{
+        config::config::{SettingsStruct, SyncJobStruct},
+        sync::sync::{deletes, sync_args},
+        utils::utils::*,
+    };
+
+    pub const CONDITIONS: [&str; 4] = ["always", "ac", "unmetered", "ac+unmetered"];
+
+    /**
+     * Allowed values of each cron field, minute hour day month weekday
+     */
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct CronStruct {
+        minutes: Vec<u32>,
+        hours: Vec<u32>,
+        days: Vec<u32>,
+        months: Vec<u32>,
+        weekdays: Vec<u32>,
+        any_day: bool,
+        any_weekday: bool,
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub enum Schedule {
+        Interval(i64),
+        Cron(CronStruct),
+    }
+
+    /**
+     * `*`, `5`, `1-5`, steps like `1-10/2` and comma lists of those
+     */
+    fn parse_field(field: &str, min: u32, max: u32) -> Result<Vec<u32>, String> {
+        let mut values = vec![];
+        for part in field.split(',') {
+            let (range, step) = match part.split_once('/') {
+                Some((range, step)) => (
+                    range,
+                    step.parse::<u32>()
+                        .ok()
+                        .filter(|s| *s > 0)
+                        .ok_or(format!("Bad step in {}", part))?,
+                ),
+                None => (part, 1),
+            };
+            let (start, end) = match range {
+                "*" => (min, max),
+                range => match range.split_once('-') {
+                    Some((start, end)) => (
+                        start.parse().map_err(|_| format!("Bad range {}", range))?,
+                        end.parse().map_err(|_| format!("Bad range {}", range))?,
+                    ),
+                    None => {
+                        let value = range.parse().map_err(|_| format!("Bad value {}", range))?;
+                        // `5/10` means from 5 to the end
+                        match part.contains('/') {
+                            true => (value, max),
+                            false => (value, value),
+                        }
+                    }
+                },
+            };
+            if start < min || end > max || start > end {
+                return Err(format!("{} is out of {}-{}", part, min, max));
+            }
+            values.extend((start..=end).step_by(step as usize));
+        }
+        values.sort_unstable();
+        values.dedup();
+        Ok(values)
+    }
+
+    fn parse_cron(expression: &str) -> Result<CronStruct, String> {
+        let expression = match expression {
+            "@hourly" => "0 * * * *",
+            "@daily" | "@midnight" => "0 0 * * *",
+            "@weekly" => "0 0 * * 0",
+            "@monthly" => "0 0 1 * *",
+            expression => expression,
+        };
+        let fields: Vec<&str> = expression.split_whitespace().collect();
+        if fields.len() != 5 {
+            return Err(String::from(
+                "A cron expression has 5 fields: minute hour day month weekday",
+            ));
+        }
+        let mut weekdays = parse_field(fields[4], 0, 7)?;
+        // 7 is sunday too
+        if weekdays.contains(&7) {
+            weekdays.retain(|d| *d != 7);
+            if !weekdays.contains(&0) {
+                weekdays.insert(0, 0);
+            }
+        }
+        Ok(CronStruct {
+            minutes: parse_field(fields[0], 0, 59)?,
+            hours: parse_field(fields[1], 0, 23)?,
+            days: parse_field(fields[2], 1, 31)?,
+            months: parse_field(fields[3], 1, 12)?,
+            weekdays,
+            any_day: fields[2] == "*",
+            any_weekday: fields[4] == "*",
+        })
+    }
+
+    /**
+     * `every 30m`, `@every 6h` or `every 1d`
+     */
+    fn parse_interval(interval: &str) -> Result<i64, String> {
+        let number = interval.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        let unit = match &interval[number.len()..] {
+            "s" => 1,
+            "m" => 60,
+            "h" => 3600,
+            "d" => 86400,
+            _ => return Err(format!("{} needs a unit: s, m, h or d", interval)),
+        };
+        match number.parse::<i64>().map(|n| n.checked_mul(unit)) {
+            Ok(Some(seconds)) if seconds >= 60 => Ok(seconds),
+            Ok(Some(_)) => Err(String::from(
+                "Intervals shorter than a minute arent supported",
+            )),
+            Ok(None) | Err(_) => Err(format!("Bad interval {}", interval)),
+        }
+    }
+
+    /**
+     * None for jobs only run by hand
+     */
+    pub fn parse_schedule(schedule: &str) -> Result<Option<Schedule>, String> {
+        let schedule = schedule.trim();
+        if schedule.is_empty() {
+            return Ok(None);
+        }
+        let interval = schedule
+            .strip_prefix("@every ")
+            .or(schedule.strip_prefix("every "));
+        match interval {
+            Some(interval) => parse_interval(interval.trim()).map(|i| Some(Schedule::Interval(i))),
+            None => parse_cron(schedule).map(|c| Some(Schedule::Cron(c))),
+        }
+    }
+
+    impl CronStruct {
+        /**
+         * Classic cron, when both day fields are set either one matches
+         */
+        fn matches_day(&self, date: NaiveDate) -> bool {
+            let day = self.days.contains(&date.day());
+            let weekday = self
+                .weekdays
+                .contains(&date.weekday().num_days_from_sunday());
+            match (self.any_day, self.any_weekday) {
+                (true, true) => true,
+                (true, false) => weekday,
+                (false, true) => day,
+                (false, false) => day || weekday,
+            }
+        }
+
+        /**
+         * First matching minute strictly after `after`, in local time
+         */
+        pub fn next_after(&self, after: NaiveDateTime) -> Option<NaiveDateTime> {
+            let mut time = after.with_second(0)?.with_nanosecond(0)? + Duration::minutes(1);
+            // the worst case, 29th of February on a weekday, comes back within years
+            let limit = after + Duration::days(366 * 8);
+            while time < limit {
+                if !self.months.contains(&time.month()) {
+                    let (year, month) = match time.month() {
+                        12 => (time.year() + 1, 1),
+                        month => (time.year(), month + 1),
+                    };
+                    time = NaiveDate::from_ymd_opt(year, month, 1)?.and_hms_opt(0, 0, 0)?;
+                } else if !self.matches_day(time.date()) {
+                    time = time.date().succ_opt()?.and_hms_opt(0, 0, 0)?;
+                } else if !self.hours.contains(&time.hour()) {
+                    time = time.with_minute(0)? + Duration::hours(1);
+                } else if !self.minutes.contains(&time.minute()) {
+                    time += Duration::minutes(1);
+                } else {
+                    return Some(time);
+                }
+            }
+            None
+        }
+    }
+
+    /**
+     * Unix time of the next run after `after`
+     */
+    pub fn next_run(schedule: &Schedule, after: i64) -> Option<i64> {
+        match schedule {
+            Schedule::Interval(seconds) => after.checked_add(*seconds),
+            Schedule::Cron(cron) => {
+                let local = Local.timestamp_opt(after, 0).single()?.naive_local();
+                let next = cron.next_after(local)?;
+                // a time skipped by daylight saving goes to the next match
+                match Local.from_local_datetime(&next).earliest() {
+                    Some(next) => Some(next.timestamp()),
+                    None => next_run(schedule, after + 3600),
+                }
+            }
+        }
+    }
+
+    /**
+     * Keeps the run times and filters of an edited job, plans the next run
+     * when the schedule changed
+     */
+    pub fn reschedule(job: &mut SyncJobStruct, previous: Option<&SyncJobStruct>, now: i64) {
+        if let Some(previous) = previous {
+            job.last_run = previous.last_run;
+            job.filter = previous.filter.clone();
+            if previous.schedule == job.schedule {
+                job.next_run = previous.next_run;
+                return;
+            }
+        }
+        job.next_run = match parse_schedule(&job.schedule) {
+            Ok(Some(schedule)) => next_run(&schedule, now),
+            _ => None,
+        };
+    }
+
+    pub fn format_time(time: i64) -> String {
+        match Local.timestamp_opt(time, 0).single() {
+            Some(time) => time.format("%Y/%m/%d %H:%M").to_string(),
+            None => String::from("?"),
+        }
+    }
+
+    pub fn now() -> i64 {
+        Local::now().timestamp()
+    }
+
+    /**
+     * Mains power, machines without a battery always are
+     */
+    fn on_ac() -> bool {
+        if cfg!(target_os = "macos") {
+            return run_command("pmset", &[String::from("-g"), String::from("batt")])
+                .map(|out| out.contains("AC Power"))
+                .unwrap_or(true);
+        }
+        let supplies = match fs::read_dir("/sys/class/power_supply") {
+            Ok(supplies) => supplies,
+            Err(_) => return true,
+        };
+        let read = |dir: &Path, file: &str| {
+            fs::read_to_string(dir.join(file))
+                .unwrap_or_default()
+                .trim()
+                .to_owned()
+        };
+        let mut battery = false;
+        for supply in supplies.filter_map(|s| s.ok()) {
+            let dir = supply.path();
+            match read(&dir, "type").as_str() {
+                "Mains" | "USB" if read(&dir, "online") == "1" => return true,
+                "Battery" => battery = true,
+                _ => {}
+            }
+        }
+        !battery
+    }
+
+    /**
+     * What NetworkManager says of the active connections, unknown counts as unmetered
+     */
+    fn unmetered() -> bool {
+        let args = ["-t", "-f", "GENERAL.METERED", "dev", "show"].map(String::from);
+        match run_command("nmcli", &args) {
+            Ok(out) => !out
+                .lines()
+                .any(|l| l.trim_start_matches("GENERAL.METERED:").starts_with("yes")),
+            Err(_) => true,
+        }
+    }
+
+    pub fn condition_met(condition: &str) -> bool {
+        match condition {
+            "ac" => on_ac(),
+            "unmetered" => unmetered(),
+            "ac+unmetered" => on_ac() && unmetered(),
+            _ => true,
+        }
+    }
+
+    /**
+     * Jobs whose run time has come, missed ones included, their next run is
+     * planned from now so a long sleep runs them once and not for every miss.
+     * Jobs waiting on a condition stay due
+     */
+    pub fn due_jobs(settings: &mut SettingsStruct, now: i64) -> Vec<SyncJobStruct> {
+        let mut due = vec![];
+        let safe_mode = settings.safe_mode;
+        for job in settings.syncs.iter_mut() {
+            let schedule = match parse_schedule(&job.schedule) {
+                Ok(Some(schedule)) => schedule,
+                _ => continue,
+            };
+            let next = match job.next_run {
+                Some(next) => next,
+                None => {
+                    job.next_run = next_run(&schedule, now);
+                    continue;
+                }
+            };
+            if next > now || !condition_met(&job.condition) {
+                continue;
+            }
+            if next < now - 60 {
+                log_warning(format!(
+                    "{} missed its run of {}, running it now",
+                    job.name,
+                    format_time(next)
+                ));
+            }
+            job.next_run = next_run(&schedule, now);
+            if safe_mode && deletes(job) {
+                log_warning(format!("Safe mode is on, skipped {}", job.name));
+                continue;
+            }
+            due.push(job.clone());
+        }
+        due.retain(|job| match settings.write_problem(&sync_args(job)) {
+            Some(problem) => {
+                log_warning(format!("{}, skipped {}", problem, job.name));
+                false
+            }
+            None => true,
+        });
+        for job in settings.syncs.iter_mut() {
+            if due.iter().any(|d| d.name == job.name) {
+                job.last_run = Some(now);
+            }
+        }
+        due
+    }
+
+    pub fn describe(job: &SyncJobStruct) -> String {
+        match (&job.schedule, job.next_run) {
+            (schedule, _) if schedule.is_empty() => String::from("by hand"),
+            (schedule, Some(next)) => format!("{}, next {}", schedule, format_time(next)),
+            (schedule, None) => schedule.clone(),
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn at(y: i32, mo: u32, d: u32, h: u32, mi: u32) -> NaiveDateTime {
+            NaiveDate::from_ymd_opt(y, mo, d)
+                .unwrap()
+                .and_hms_opt(h, mi, 0)
+                .unwrap()
+        }
+
+        #[test]
+        fn parses_fields_ranges_and_steps() {
+            let cron = parse_cron("*/15 1-3 * * 1,5").unwrap();
+            assert_eq!(cron.minutes, vec![0, 15, 30, 45]);
+            assert_eq!(cron.hours, vec![1, 2, 3]);
+            assert_eq!(cron.weekdays, vec![1, 5]);
+            assert!(cron.any_day && !cron.any_weekday);
+            assert_eq!(parse_cron("5/20 * * * *").unwrap().minutes, vec![5, 25, 45]);
+        }
+
+        #[test]
+        fn sunday_is_zero_or_seven() {
+            assert_eq!(parse_cron("0 0 * * 7").unwrap().weekdays, vec![0]);
+            assert_eq!(
+                parse_cron("@weekly").unwrap(),
+                parse_cron("0 0 * * 0").unwrap()
+            );
+        }
+
+        #[test]
+        fn rejects_bad_expressions() {
+            assert!(parse_cron("* * * *").is_err());
+            assert!(parse_cron("60 * * * *").is_err());
+            assert!(parse_cron("5-1 * * * *").is_err());
+            assert!(parse_cron("*/0 * * * *").is_err());
+            assert!(parse_cron("a * * * *").is_err());
+        }
+
+        #[test]
+        fn next_after_is_strictly_later() {
+            let cron = parse_cron("30 2 * * *").unwrap();
+            assert_eq!(
+                cron.next_after(at(2024, 1, 1, 1, 0)),
+                Some(at(2024, 1, 1, 2, 30))
+            );
+            assert_eq!(
+                cron.next_after(at(2024, 1, 1, 2, 30)),
+                Some(at(2024, 1, 2, 2, 30))
+            );
+        }
+
+        #[test]
+        fn next_after_rolls_over_months_and_years() {
+            let cron = parse_cron("0 0 1 * *").unwrap();
+            assert_eq!(
+                cron.next_after(at(2024, 12, 15, 8, 0)),
+                Some(at(2025, 1, 1, 0, 0))
+            );
+            let leap = parse_cron("0 12 29 2 *").unwrap();
+            assert_eq!(
+                leap.next_after(at(2024, 3, 1, 0, 0)),
+                Some(at(2028, 2, 29, 12, 0))
+            );
+        }
+
+        #[test]
+        fn either_day_field_matches_when_both_are_set() {
+            // the 13th or any friday, 2024-09-06 is a friday
+            let cron = parse_cron("0 0 13 * 5").unwrap();
+            assert_eq!(
+                cron.next_after(at(2024, 9, 1, 0, 0)),
+                Some(at(2024, 9, 6, 0, 0))
+            );
+            assert_eq!(
+                cron.next_after(at(2024, 9, 12, 0, 0)),
+                Some(at(2024, 9, 13, 0, 0))
+            );
+        }
+
+        #[test]
+        fn parses_intervals() {
+            assert_eq!(
+                parse_schedule("every 30m"),
+                Ok(Some(Schedule::Interval(1800)))
+            );
+            assert_eq!(
+                parse_schedule("@every 1d"),
+                Ok(Some(Schedule::Interval(86400)))
+            );
+            assert_eq!(parse_schedule(""), Ok(None));
+            assert!(parse_schedule("every 30s").is_err());
+            assert!(parse_schedule("every 5").is_err());
+            assert_eq!(
+                parse_schedule("every 9999999999999999d"),
+                Err(String::from("Bad interval 9999999999999999d"))
+            );
+        }
+    }
+}
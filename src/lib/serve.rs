@@ -0,0 +1,162 @@
+#![allow(dead_code)]
+
+pub mod serve {
+    use std::{
+        io::{BufRead, BufReader},
+        process::{Child, Command, Stdio},
+        thread,
+    };
+
+    use crate::lib::{
+        app::app_mod::App, config::config::ServeOptionsStruct, form::form::*, utils::utils::*,
+    };
+
+    pub const PROTOCOLS: [&str; 6] = ["webdav", "http", "sftp", "ftp", "dlna", "nfs"];
+
+    pub fn default_port(protocol: &str) -> &'static str {
+        match protocol {
+            "sftp" => "2022",
+            "ftp" => "2121",
+            "dlna" => "7879",
+            "nfs" => "2049",
+            _ => "8080",
+        }
+    }
+
+    /**
+     * dlna and nfs have no authentication in rclone
+     */
+    pub fn supports_auth(protocol: &str) -> bool {
+        !matches!(protocol, "dlna" | "nfs")
+    }
+
+    pub fn serve_args(options: &ServeOptionsStruct) -> Vec<String> {
+        let mut args = vec![
+            String::from("serve"),
+            options.protocol.clone(),
+            format!("{}:", options.name),
+            String::from("--addr"),
+            format!("{}:{}", options.addr, options.port),
+        ];
+        if supports_auth(&options.protocol) && !options.user.is_empty() {
+            args.push(String::from("--user"));
+            args.push(options.user.clone());
+        }
+        if options.read_only {
+            args.push(String::from("--read-only"));
+        }
+        args
+    }
+
+    /**
+     * The password goes through the environment, argv is readable by every user
+     */
+    pub fn serve_envs(options: &ServeOptionsStruct) -> Vec<(&'static str, String)> {
+        match supports_auth(&options.protocol) && !options.user.is_empty() {
+            true => vec![("RCLONE_PASS", options.pass.clone())],
+            false => vec![],
+        }
+    }
+
+    pub fn listen_url(options: &ServeOptionsStruct) -> String {
+        let host = match options.addr.as_str() {
+            "" | "0.0.0.0" => "localhost",
+            addr => addr,
+        };
+        match options.protocol.as_str() {
+            "webdav" | "http" => format!("http://{}:{}/", host, options.port),
+            "sftp" | "ftp" if !options.user.is_empty() => format!(
+                "{}://{}@{}:{}/",
+                options.protocol, options.user, host, options.port
+            ),
+            "nfs" => format!("nfs://{}:{}/", host, options.port),
+            "dlna" => format!("dlna {}:{}", host, options.port),
+            protocol => format!("{}://{}:{}/", protocol, host, options.port),
+        }
+    }
+
+    pub fn serve_form(options: &ServeOptionsStruct) -> FormStruct {
+        FormStruct::with_fields(vec![
+            FieldStruct::new(
+                "protocol",
+                "Protocol",
+                &options.protocol,
+                FieldKind::Choice(PROTOCOLS.iter().map(|p| p.to_string()).collect()),
+            ),
+            FieldStruct::new("addr", "Address", &options.addr, FieldKind::Text),
+            FieldStruct::new("port", "Port", &options.port, FieldKind::Text),
+            FieldStruct::new("user", "User", &options.user, FieldKind::Text),
+            FieldStruct::new("pass", "Password", &options.pass, FieldKind::Secret),
+            FieldStruct::new(
+                "read_only",
+                "Read only",
+                &options.read_only.to_string(),
+                FieldKind::Toggle,
+            ),
+        ])
+    }
+
+    pub fn options_from_form(name: &str, form: &FormStruct) -> ServeOptionsStruct {
+        ServeOptionsStruct {
+            name: name.to_owned(),
+            protocol: form.value("protocol"),
+            addr: form.value("addr"),
+            port: form.value("port"),
+            user: form.value("user"),
+            pass: form.value("pass"),
+            read_only: form.toggled("read_only"),
+        }
+    }
+
+    pub fn start_serving(options: &ServeOptionsStruct, app: &mut App) -> bool {
+        log_info(format!(
+            "Start serving {} over {}",
+            options.name, options.protocol
+        ));
+        match Command::new("rclone")
+            .args(serve_args(options))
+            .envs(serve_envs(options))
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .spawn()
+        {
+            Ok(mut child) => {
+                if let Some(stderr) = child.stderr.take() {
+                    let name = options.name.clone();
+                    thread::spawn(move || {
+                        for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                            match line.contains("ERROR") || line.contains("Fatal") {
+                                true => log_error(format!("{}: {}", name, line)),
+                                false => log_info(format!("{}: {}", name, line)),
+                            }
+                        }
+                    });
+                }
+                log_info(format!(
+                    "{} listening on {}",
+                    options.name,
+                    listen_url(options)
+                ));
+                app.serves_running.push(options.clone());
+                app.processes_served.push(child);
+                true
+            }
+            Err(e) => {
+                log_error(e.to_string());
+                false
+            }
+        }
+    }
+
+    pub fn stop_serving(options: &ServeOptionsStruct, mut process: Child) {
+        log_warning(format!(
+            "Stop serving {} over {}",
+            options.name, options.protocol
+        ));
+        match process.kill() {
+            Ok(_) => log_debug(format!("Killed process ID: {}", process.id())),
+            Err(e) => log_error(e.to_string()),
+        }
+        let _ = process.wait();
+    }
+}
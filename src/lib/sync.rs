@@ -0,0 +1,356 @@
+#![allow(dead_code)]
+
+pub mod sync {
+    use std::{
+        io::{BufRead, BufReader},
+        process::{Child, Command, Stdio},
+        sync::mpsc::{self, Receiver},
+        thread,
+    };
+
+    use crate::lib::{
+        config::config::{FilterSetStruct, SyncJobStruct},
+        filter::filter::filter_args,
+        form::form::*,
+        schedule::schedule::{parse_schedule, CONDITIONS},
+        utils::utils::*,
+    };
+
+    pub const MODES: [&str; 4] = ["copy", "sync", "bisync", "check"];
+
+    pub fn sync_form(job: Option<&SyncJobStruct>) -> FormStruct {
+        let job = job.cloned().unwrap_or(SyncJobStruct {
+            name: String::new(),
+            source: String::new(),
+            destination: String::new(),
+            mode: String::from("copy"),
+            filter: FilterSetStruct::default(),
+            flags: vec![],
+            schedule: String::new(),
+            condition: String::from("always"),
+            last_run: None,
+            next_run: None,
+        });
+        FormStruct::with_fields(vec![
+            FieldStruct::new("name", "Name", &job.name, FieldKind::Text),
+            FieldStruct::new("source", "Source", &job.source, FieldKind::Text)
+                .with_help("Local folder or remote:path"),
+            FieldStruct::new(
+                "destination",
+                "Destination",
+                &job.destination,
+                FieldKind::Text,
+            )
+            .with_help("Local folder or remote:path"),
+            FieldStruct::new(
+                "mode",
+                "Mode",
+                &job.mode,
+                FieldKind::Choice(MODES.iter().map(|m| m.to_string()).collect()),
+            )
+            .with_help("copy never deletes, sync mirrors, bisync works both ways, check compares"),
+            FieldStruct::new("flags", "Flags", &job.flags.join(" "), FieldKind::Text)
+                .with_help("Extra rclone flags, e.g. --fast-list --transfers 8"),
+            FieldStruct::new("schedule", "Schedule", &job.schedule, FieldKind::Text)
+                .with_help("Cron like 0 3 * * *, @daily or every 6h, empty runs by hand"),
+            FieldStruct::new(
+                "condition",
+                "Only when",
+                &job.condition,
+                FieldKind::Choice(CONDITIONS.iter().map(|c| c.to_string()).collect()),
+            )
+            .with_help("Scheduled runs wait for mains power or a connection not marked metered"),
+        ])
+    }
+
+    pub fn sync_from_form(
+        form: &FormStruct,
+        syncs: &[SyncJobStruct],
+        previous: Option<&str>,
+    ) -> Result<SyncJobStruct, String> {
+        let job = SyncJobStruct {
+            name: form.value("name").trim().to_owned(),
+            source: form.value("source").trim().to_owned(),
+            destination: form.value("destination").trim().to_owned(),
+            mode: form.value("mode"),
+            filter: FilterSetStruct::default(),
+            flags: form
+                .value("flags")
+                .split_whitespace()
+                .map(String::from)
+                .collect(),
+            schedule: form.value("schedule").trim().to_owned(),
+            condition: form.value("condition"),
+            last_run: None,
+            next_run: None,
+        };
+        if job.name.is_empty() {
+            return Err(String::from("A sync job needs a name"));
+        }
+        // runs, previews and filters find jobs by name
+        if previous != Some(job.name.as_str()) && syncs.iter().any(|s| s.name == job.name) {
+            return Err(format!("A sync job named {} exists", job.name));
+        }
+        if job.source.is_empty() || job.destination.is_empty() {
+            return Err(String::from("Source and destination are both needed"));
+        }
+        if job.source == job.destination {
+            return Err(String::from("Source and destination are the same"));
+        }
+        parse_schedule(&job.schedule)?;
+        Ok(job)
+    }
+
+    /**
+     * Deletes files on the destination, or on both sides for bisync
+     */
+    pub fn deletes(job: &SyncJobStruct) -> bool {
+        matches!(job.mode.as_str(), "sync" | "bisync")
+    }
+
+    pub fn sync_label(job: &SyncJobStruct) -> String {
+        format!("{} {} → {}", job.mode, job.source, job.destination)
+    }
+
+    pub fn sync_args(job: &SyncJobStruct) -> Vec<String> {
+        let mut args = vec![
+            job.mode.clone(),
+            job.source.clone(),
+            job.destination.clone(),
+        ];
+        args.extend(filter_args(&job.filter));
+        args.extend(job.flags.iter().cloned());
+        args
+    }
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum PreviewAction {
+        Create,
+        Update,
+        Delete,
+    }
+
+    impl PreviewAction {
+        pub fn symbol(&self) -> &'static str {
+            match self {
+                PreviewAction::Create => "+",
+                PreviewAction::Update => "*",
+                PreviewAction::Delete => "-",
+            }
+        }
+    }
+
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct PreviewEntryStruct {
+        pub action: PreviewAction,
+        pub path: String,
+    }
+
+    /**
+     * A running `--dry-run` of a sync job, what it would change arrives line by line
+     */
+    pub struct PreviewStruct {
+        pub name: String,
+        /**
+         * Arguments the dry run checked, the job only runs while they stay the same
+         */
+        pub args: Vec<String>,
+        pub entries: Vec<PreviewEntryStruct>,
+        pub done: bool,
+        pub failed: bool,
+        process: Child,
+        rx: Receiver<PreviewEntryStruct>,
+    }
+
+    /**
+     * `--combined` report of copy and sync, `+` missing on the destination,
+     * `*` different, `-` only on the destination so only sync removes it
+     */
+    fn parse_combined(line: &str, mode: &str) -> Option<PreviewEntryStruct> {
+        let (symbol, path) = line.split_once(' ')?;
+        let action = match symbol {
+            "+" => PreviewAction::Create,
+            "*" => PreviewAction::Update,
+            "-" if mode == "sync" => PreviewAction::Delete,
+            _ => return None,
+        };
+        Some(PreviewEntryStruct {
+            action,
+            path: path.to_owned(),
+        })
+    }
+
+    /**
+     * bisync has no report, its log queues copies and deletes on either side
+     */
+    fn parse_bisync(line: &str) -> Option<PreviewEntryStruct> {
+        let action = match line {
+            l if l.contains("Queue copy to") => PreviewAction::Update,
+            l if l.contains("Queue delete") => PreviewAction::Delete,
+            _ => return None,
+        };
+        let (_, path) = line.rsplit_once(" - ")?;
+        Some(PreviewEntryStruct {
+            action,
+            path: path.trim().to_owned(),
+        })
+    }
+
+    pub fn start_preview(job: &SyncJobStruct) -> Result<PreviewStruct, String> {
+        let mut args = sync_args(job);
+        args.push(String::from("--dry-run"));
+        let bisync = job.mode == "bisync";
+        if !bisync {
+            args.push(String::from("--combined"));
+            args.push(String::from("-"));
+        }
+        log_info(format!("Dry run of {}", job.name));
+        log_trace(format!("rclone {}", args.join(" ")));
+        let mut process = Command::new("rclone")
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| e.to_string())?;
+        let (tx, rx) = mpsc::channel::<PreviewEntryStruct>();
+        if let Some(stdout) = process.stdout.take() {
+            let tx = tx.clone();
+            let mode = job.mode.clone();
+            thread::spawn(move || {
+                for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                    if let Some(entry) = parse_combined(&line, &mode) {
+                        if tx.send(entry).is_err() {
+                            break;
+                        }
+                    }
+                }
+            });
+        }
+        if let Some(stderr) = process.stderr.take() {
+            thread::spawn(move || {
+                for line in BufReader::new(stderr).lines().map_while(Result::ok) {
+                    let entry = match bisync {
+                        true => parse_bisync(&line),
+                        false => None,
+                    };
+                    match entry.map(|entry| tx.send(entry)) {
+                        Some(Err(_)) => break,
+                        Some(Ok(_)) => {}
+                        // dry runs print a notice per skipped file
+                        None if line.contains("ERROR") => log_error(line),
+                        None => {}
+                    }
+                }
+            });
+        }
+        Ok(PreviewStruct {
+            name: job.name.clone(),
+            args: sync_args(job),
+            entries: vec![],
+            done: false,
+            failed: false,
+            process,
+            rx,
+        })
+    }
+
+    impl PreviewStruct {
+        /**
+         * Takes new entries, marks the preview done once rclone exits
+         */
+        pub fn poll(&mut self) {
+            self.entries.extend(self.rx.try_iter());
+            if self.done {
+                return;
+            }
+            match self.process.try_wait() {
+                Ok(Some(status)) => {
+                    self.entries.extend(self.rx.iter());
+                    self.done = true;
+                    self.failed = !status.success();
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    log_error(e.to_string());
+                    self.done = true;
+                    self.failed = true;
+                }
+            }
+        }
+
+        pub fn cancel(&mut self) {
+            if !self.done {
+                let _ = self.process.kill();
+                let _ = self.process.wait();
+                self.done = true;
+                self.failed = true;
+            }
+        }
+
+        pub fn count(&self, action: PreviewAction) -> usize {
+            self.entries.iter().filter(|e| e.action == action).count()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn entry(action: PreviewAction, path: &str) -> Option<PreviewEntryStruct> {
+            Some(PreviewEntryStruct {
+                action,
+                path: path.to_owned(),
+            })
+        }
+
+        #[test]
+        fn refuses_taken_names_but_its_own() {
+            let mut form = sync_form(None);
+            form.set("name", "photos");
+            form.set("source", "/home/me/photos");
+            form.set("destination", "gdrive:photos");
+            let photos = sync_from_form(&form, &[], None).unwrap();
+            let syncs = vec![photos.clone()];
+            assert!(sync_from_form(&form, &syncs, None).is_err());
+            assert!(sync_from_form(&form, &syncs, Some("photos")).is_ok());
+            let mut other = photos;
+            other.name = String::from("docs");
+            let syncs = vec![syncs[0].clone(), other];
+            assert!(sync_from_form(&form, &syncs, Some("docs")).is_err());
+        }
+
+        #[test]
+        fn parses_the_combined_report() {
+            assert_eq!(
+                parse_combined("+ photos/a b.jpg", "copy"),
+                entry(PreviewAction::Create, "photos/a b.jpg")
+            );
+            assert_eq!(
+                parse_combined("* notes.txt", "sync"),
+                entry(PreviewAction::Update, "notes.txt")
+            );
+            assert_eq!(
+                parse_combined("- old.txt", "sync"),
+                entry(PreviewAction::Delete, "old.txt")
+            );
+            // copy never removes what is only on the destination
+            assert_eq!(parse_combined("- old.txt", "copy"), None);
+            assert_eq!(parse_combined("= same.txt", "sync"), None);
+            assert_eq!(parse_combined("garbage", "sync"), None);
+        }
+
+        #[test]
+        fn parses_bisync_queues() {
+            assert_eq!(
+                parse_bisync("INFO  : - Path1    Queue copy to Path2   - b:docs/a.txt"),
+                entry(PreviewAction::Update, "b:docs/a.txt")
+            );
+            assert_eq!(
+                parse_bisync("INFO  : - Path2    Queue delete          - a:docs/old.txt"),
+                entry(PreviewAction::Delete, "a:docs/old.txt")
+            );
+            assert_eq!(parse_bisync("INFO  : Bisync successful"), None);
+        }
+    }
+}
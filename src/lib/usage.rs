@@ -0,0 +1,334 @@
+#![allow(dead_code)]
+
+pub mod usage {
+    use std::collections::HashMap;
+
+    use crate::lib::{
+        browser::browser::{
+            child_path, parent_path, start_listing_with, FileStruct, ListingStruct,
+        },
+        utils::utils::*,
+    };
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    pub enum UsageSort {
+        Size,
+        Count,
+    }
+
+    impl UsageSort {
+        pub fn next(&self) -> UsageSort {
+            match self {
+                UsageSort::Size => UsageSort::Count,
+                UsageSort::Count => UsageSort::Size,
+            }
+        }
+
+        pub fn label(&self) -> &'static str {
+            match self {
+                UsageSort::Size => "size",
+                UsageSort::Count => "files",
+            }
+        }
+    }
+
+    /**
+     * A file or a folder with the size and file count of everything below it
+     */
+    #[derive(Debug, Clone, PartialEq)]
+    pub struct UsageEntryStruct {
+        pub name: String,
+        pub path: String,
+        pub is_dir: bool,
+        pub size: u64,
+        pub count: u64,
+    }
+
+    /**
+     * Sizes of a scanned tree, paths are relative to the scanned folder
+     */
+    #[derive(Debug, Default)]
+    pub struct UsageTreeStruct {
+        pub size: u64,
+        pub count: u64,
+        nodes: HashMap<String, UsageEntryStruct>,
+        children: HashMap<String, Vec<String>>,
+    }
+
+    fn ancestors(path: &str) -> Vec<String> {
+        let mut dirs = vec![];
+        let mut dir = parent_path(path);
+        while !dir.is_empty() {
+            let parent = parent_path(&dir);
+            dirs.push(dir);
+            dir = parent;
+        }
+        dirs
+    }
+
+    impl UsageTreeStruct {
+        /**
+         * Creates a folder and the folders above it when they arent known yet
+         */
+        fn ensure_dir(&mut self, path: &str) {
+            if path.is_empty() || self.nodes.contains_key(path) {
+                return;
+            }
+            let parent = parent_path(path);
+            self.ensure_dir(&parent);
+            let name = path.rsplit('/').next().unwrap_or(path).to_owned();
+            self.nodes.insert(
+                path.to_owned(),
+                UsageEntryStruct {
+                    name,
+                    path: path.to_owned(),
+                    is_dir: true,
+                    size: 0,
+                    count: 0,
+                },
+            );
+            self.children
+                .entry(parent)
+                .or_default()
+                .push(path.to_owned());
+        }
+
+        /**
+         * Adds one lsjson entry, files count towards every folder above them
+         */
+        pub fn add(&mut self, file: &FileStruct) {
+            let path = file.path.trim_matches('/').to_owned();
+            if path.is_empty() {
+                return;
+            }
+            if file.is_dir {
+                self.ensure_dir(&path);
+                return;
+            }
+            if self.nodes.contains_key(&path) {
+                return;
+            }
+            let parent = parent_path(&path);
+            self.ensure_dir(&parent);
+            let size = file.size.max(0) as u64;
+            self.nodes.insert(
+                path.clone(),
+                UsageEntryStruct {
+                    name: file.name.clone(),
+                    path: path.clone(),
+                    is_dir: false,
+                    size,
+                    count: 1,
+                },
+            );
+            self.children.entry(parent).or_default().push(path.clone());
+            for dir in ancestors(&path) {
+                if let Some(node) = self.nodes.get_mut(&dir) {
+                    node.size += size;
+                    node.count += 1;
+                }
+            }
+            self.size += size;
+            self.count += 1;
+        }
+
+        pub fn get(&self, path: &str) -> Option<&UsageEntryStruct> {
+            self.nodes.get(path)
+        }
+
+        /**
+         * Size and file count of a folder, the scanned folder itself when empty
+         */
+        pub fn totals(&self, dir: &str) -> (u64, u64) {
+            match self.nodes.get(dir) {
+                Some(node) => (node.size, node.count),
+                None => (self.size, self.count),
+            }
+        }
+
+        /**
+         * What a folder holds, biggest first
+         */
+        pub fn entries(&self, dir: &str, sort: UsageSort) -> Vec<UsageEntryStruct> {
+            let mut entries: Vec<UsageEntryStruct> = match self.children.get(dir) {
+                Some(paths) => paths
+                    .iter()
+                    .filter_map(|p| self.nodes.get(p))
+                    .cloned()
+                    .collect(),
+                None => vec![],
+            };
+            entries.sort_by(|a, b| match sort {
+                UsageSort::Size => b.size.cmp(&a.size).then(b.count.cmp(&a.count)),
+                UsageSort::Count => b.count.cmp(&a.count).then(b.size.cmp(&a.size)),
+            });
+            entries
+        }
+
+        /**
+         * Forgets a deleted entry and everything below it, the folders above shrink
+         */
+        pub fn remove(&mut self, path: &str) {
+            let node = match self.nodes.remove(path) {
+                Some(node) => node,
+                None => return,
+            };
+            for dir in ancestors(path) {
+                if let Some(parent) = self.nodes.get_mut(&dir) {
+                    parent.size = parent.size.saturating_sub(node.size);
+                    parent.count = parent.count.saturating_sub(node.count);
+                }
+            }
+            self.size = self.size.saturating_sub(node.size);
+            self.count = self.count.saturating_sub(node.count);
+            if let Some(siblings) = self.children.get_mut(&parent_path(path)) {
+                siblings.retain(|p| p != path);
+            }
+            let prefix = format!("{}/", path);
+            self.nodes.retain(|p, _| !p.starts_with(&prefix));
+            self.children
+                .retain(|p, _| p != path && !p.starts_with(&prefix));
+        }
+    }
+
+    /**
+     * A recursive `rclone lsjson` feeding a size tree, like `rclone ncdu`
+     */
+    pub struct UsageScanStruct {
+        pub remote: String,
+        pub root: String,
+        pub tree: UsageTreeStruct,
+        pub cancelled: bool,
+        /**
+         * Delete jobs by id with the path they remove from the tree once done
+         */
+        pub deleting: Vec<(usize, String)>,
+        listing: ListingStruct,
+    }
+
+    impl UsageScanStruct {
+        pub fn start(remote: &str, root: &str) -> Result<UsageScanStruct, String> {
+            let listing = start_listing_with(remote, root, &[String::from("-R")])?;
+            log_info(format!("Scanning sizes of {}:{}", remote, root));
+            Ok(UsageScanStruct {
+                remote: remote.to_owned(),
+                root: root.to_owned(),
+                tree: UsageTreeStruct::default(),
+                cancelled: false,
+                deleting: vec![],
+                listing,
+            })
+        }
+
+        pub fn poll(&mut self) {
+            let was_done = self.listing.done;
+            for file in self.listing.poll() {
+                self.tree.add(&file);
+            }
+            if self.listing.done && !was_done {
+                log_info(format!(
+                    "Scanned {}:{}, {} in {} files",
+                    self.remote,
+                    self.root,
+                    format_size(self.tree.size),
+                    self.tree.count
+                ));
+            }
+        }
+
+        pub fn done(&self) -> bool {
+            self.listing.done
+        }
+
+        pub fn failed(&self) -> bool {
+            self.listing.failed
+        }
+
+        pub fn cancel(&mut self) {
+            if !self.listing.done {
+                self.listing.cancel();
+                self.cancelled = true;
+            }
+        }
+
+        /**
+         * Full rclone path of an entry of the tree
+         */
+        pub fn target(&self, path: &str) -> String {
+            format!("{}:{}", self.remote, child_path(&self.root, path))
+        }
+
+        /**
+         * `purge` for folders, `deletefile` for files
+         */
+        pub fn delete_args(&self, entry: &UsageEntryStruct) -> Vec<String> {
+            let command = match entry.is_dir {
+                true => "purge",
+                false => "deletefile",
+            };
+            vec![String::from(command), self.target(&entry.path)]
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        fn file(path: &str, size: i64, is_dir: bool) -> FileStruct {
+            FileStruct {
+                name: path.rsplit('/').next().unwrap().to_owned(),
+                path: path.to_owned(),
+                size,
+                mod_time: None,
+                mime: String::new(),
+                is_dir,
+            }
+        }
+
+        fn tree() -> UsageTreeStruct {
+            let mut tree = UsageTreeStruct::default();
+            tree.add(&file("a/b/big.bin", 100, false));
+            tree.add(&file("a/small.txt", 10, false));
+            tree.add(&file("c.txt", 5, false));
+            tree.add(&file("empty", -1, true));
+            tree
+        }
+
+        #[test]
+        fn files_count_towards_every_folder_above() {
+            let tree = tree();
+            assert_eq!((tree.size, tree.count), (115, 3));
+            assert_eq!(tree.totals("a"), (110, 2));
+            assert_eq!(tree.totals("a/b"), (100, 1));
+            assert_eq!(tree.totals(""), (115, 3));
+            let names: Vec<String> = tree
+                .entries("", UsageSort::Size)
+                .into_iter()
+                .map(|e| e.name)
+                .collect();
+            assert_eq!(names, vec!["a", "c.txt", "empty"]);
+        }
+
+        #[test]
+        fn adding_a_file_twice_counts_once() {
+            let mut tree = tree();
+            tree.add(&file("a/small.txt", 10, false));
+            assert_eq!(tree.totals("a"), (110, 2));
+        }
+
+        #[test]
+        fn removing_shrinks_the_folders_above() {
+            let mut tree = tree();
+            tree.remove("a/b");
+            assert_eq!((tree.size, tree.count), (15, 2));
+            assert_eq!(tree.totals("a"), (10, 1));
+            assert!(tree.get("a/b/big.bin").is_none());
+            assert!(tree
+                .entries("a", UsageSort::Size)
+                .iter()
+                .all(|e| e.name != "b"));
+            tree.remove("missing");
+            assert_eq!((tree.size, tree.count), (15, 2));
+        }
+    }
+}
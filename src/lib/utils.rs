@@ -1,29 +1,141 @@
 #![allow(dead_code)]
 
 pub mod utils {
-    use std::{
-        process::{Command, Stdio},
-        thread,
-    };
+    use std::process::Command;
+
+    use rand::{distributions::Alphanumeric, thread_rng, Rng};
 
     use log::{debug, error, info, trace, warn, LevelFilter};
 
-    pub fn login_google_drive(name: String) {
-        thread::spawn(move || {
-            Command::new("rclone")
-                .args(&[
-                    String::from("config"),
-                    String::from("create"),
-                    format!("{}", name.trim()),
-                    String::from("drive"),
-                    String::from("config_is_local"),
-                    String::from("true"),
-                ])
-                .stdout(Stdio::piped())
-                .stderr(Stdio::piped())
-                .spawn()
-                .unwrap();
-        });
+    use crate::lib::config::config::is_secret;
+
+    /**
+     * Runs rclone to completion, Ok with stdout or Err with stderr
+     */
+    pub fn run_rclone(args: &[String]) -> Result<String, String> {
+        run_command("rclone", args)
+    }
+
+    pub fn run_command(program: &str, args: &[String]) -> Result<String, String> {
+        run_command_with(program, args, &[])
+    }
+
+    /**
+     * Same with environment variables, for secrets that shouldnt show up in `ps`
+     */
+    pub fn run_command_with(
+        program: &str,
+        args: &[String],
+        envs: &[(&str, &str)],
+    ) -> Result<String, String> {
+        // values following a secret key are only obscured, keep them out of the logs
+        let logged: Vec<&str> = args
+            .iter()
+            .enumerate()
+            .map(|(i, arg)| match i > 0 && is_secret(&args[i - 1]) {
+                true => "***",
+                false => arg.as_str(),
+            })
+            .collect();
+        log_trace(format!("{} {}", program, logged.join(" ")));
+        match Command::new(program)
+            .args(args)
+            .envs(envs.iter().copied())
+            .output()
+        {
+            Ok(output) if output.status.success() => {
+                Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            }
+            Ok(output) => Err(String::from_utf8_lossy(&output.stderr).trim().to_string()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /**
+     * Random letters and digits, for throwaway credentials
+     */
+    pub fn random_token(length: usize) -> String {
+        thread_rng()
+            .sample_iter(&Alphanumeric)
+            .take(length)
+            .map(char::from)
+            .collect()
+    }
+
+    /**
+     * `10G`, `512M`, `1.5k` or plain bytes
+     */
+    pub fn parse_size(size: &str) -> Option<u64> {
+        let size = size.trim();
+        let number = size.trim_end_matches(|c: char| c.is_ascii_alphabetic());
+        let multiplier: u64 = match size[number.len()..].to_uppercase().as_str() {
+            "" | "B" => 1,
+            "K" | "KB" | "KIB" => 1 << 10,
+            "M" | "MB" | "MIB" => 1 << 20,
+            "G" | "GB" | "GIB" => 1 << 30,
+            "T" | "TB" | "TIB" => 1 << 40,
+            "P" | "PB" | "PIB" => 1 << 50,
+            _ => return None,
+        };
+        number
+            .parse::<f64>()
+            .ok()
+            .map(|n| (n * multiplier as f64) as u64)
+    }
+
+    pub fn format_size(bytes: u64) -> String {
+        let units = ["B", "KiB", "MiB", "GiB", "TiB", "PiB"];
+        let mut size = bytes as f64;
+        let mut unit = 0;
+        while size >= 1024.0 && unit < units.len() - 1 {
+            size /= 1024.0;
+            unit += 1;
+        }
+        match unit {
+            0 => format!("{} {}", bytes, units[0]),
+            _ => format!("{:.1} {}", size, units[unit]),
+        }
+    }
+
+    pub fn format_duration(seconds: u64) -> String {
+        match seconds {
+            s if s < 60 => format!("{}s", s),
+            s if s < 3600 => format!("{}m {}s", s / 60, s % 60),
+            s if s < 86400 => format!("{}h {}m", s / 3600, (s % 3600) / 60),
+            s => format!("{}d {}h", s / 86400, (s % 86400) / 3600),
+        }
+    }
+
+    /**
+     * Standard base64 with `=` padding
+     */
+    pub fn base64(data: &[u8]) -> String {
+        const ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+        let mut encoded = String::new();
+        for chunk in data.chunks(3) {
+            let bytes = [
+                chunk[0],
+                *chunk.get(1).unwrap_or(&0),
+                *chunk.get(2).unwrap_or(&0),
+            ];
+            let n = (bytes[0] as u32) << 16 | (bytes[1] as u32) << 8 | bytes[2] as u32;
+            for i in 0..4 {
+                if i <= chunk.len() {
+                    encoded.push(ALPHABET[(n >> (18 - 6 * i) & 63) as usize] as char);
+                } else {
+                    encoded.push('=');
+                }
+            }
+        }
+        encoded
+    }
+
+    /**
+     * OSC 52 escape sequence asking the terminal to put text in the clipboard,
+     * works over SSH too
+     */
+    pub fn osc52(text: &str) -> String {
+        format!("\x1b]52;c;{}\x07", base64(text.as_bytes()))
     }
 
     pub fn log_error(msg: String) {
@@ -56,4 +168,34 @@ pub mod utils {
             _ => String::new(),
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+
+        #[test]
+        fn encodes_base64_with_padding() {
+            assert_eq!(base64(b""), "");
+            assert_eq!(base64(b"f"), "Zg==");
+            assert_eq!(base64(b"fo"), "Zm8=");
+            assert_eq!(base64(b"foo"), "Zm9v");
+            assert_eq!(base64(b"{\"scope\":\"drive\"}"), "eyJzY29wZSI6ImRyaXZlIn0=");
+        }
+
+        #[test]
+        fn wraps_text_in_osc52() {
+            assert_eq!(osc52("hi"), "\x1b]52;c;aGk=\x07");
+        }
+
+        #[test]
+        fn parses_sizes() {
+            assert_eq!(parse_size("512"), Some(512));
+            assert_eq!(parse_size("1k"), Some(1024));
+            assert_eq!(parse_size("1.5K"), Some(1536));
+            assert_eq!(parse_size(" 10G "), Some(10 << 30));
+            assert_eq!(parse_size("2MiB"), Some(2 << 20));
+            assert_eq!(parse_size("1X"), None);
+            assert_eq!(parse_size("G"), None);
+        }
+    }
 }
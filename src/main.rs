@@ -1,21 +1,66 @@
+#![allow(clippy::module_inception)]
+
 mod lib {
     pub mod app;
+    pub mod auth;
+    pub mod browser;
+    pub mod bwlimit;
+    pub mod cache;
+    pub mod commander;
+    pub mod composer;
     pub mod config;
+    pub mod crypt;
+    pub mod daemon;
+    pub mod filter;
+    pub mod form;
+    pub mod fstab;
+    pub mod gdrive;
+    pub mod history;
+    pub mod jobs;
     pub mod mount;
+    pub mod picker;
+    pub mod providers;
+    pub mod quota;
+    pub mod remote;
+    pub mod schedule;
+    pub mod serve;
+    pub mod sync;
+    pub mod usage;
     pub mod utils;
 }
 
 mod ui {
+    pub mod auth_ui;
+    pub mod browser_ui;
+    pub mod cache_ui;
+    pub mod commander_ui;
     pub mod drive_ui;
     pub mod error_ui;
+    pub mod filter_ui;
+    pub mod form_ui;
+    pub mod fstab_ui;
+    pub mod headless_ui;
+    pub mod history_ui;
+    pub mod jobs_ui;
     pub mod main_ui;
+    pub mod picker_ui;
+    pub mod serve_ui;
+    pub mod sync_ui;
+    pub mod usage_ui;
+    pub mod wizard_ui;
 }
 
-use std::io;
+use std::{env, io};
 
-use lib::app::app_mod::App;
+use lib::{app::app_mod::App, daemon::daemon};
 
 fn main() -> Result<(), io::Error> {
+    // scheduled jobs without a terminal, e.g. from a systemd user unit
+    if env::args().any(|arg| arg == "--daemon") {
+        daemon::run();
+        return Ok(());
+    }
+
     let mut app: App = App::new();
 
     let res = app.start();
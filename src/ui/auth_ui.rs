@@ -0,0 +1,167 @@
+#![allow(unused_doc_comments)]
+
+pub mod auth_ui {
+    use qrcodegen::{QrCode, QrCodeEcc};
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Direction, Layout},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, Paragraph, Wrap},
+        Frame,
+    };
+
+    use crate::lib::auth::auth::{is_loopback, AuthStatus, AuthStruct};
+
+    /**
+     * Draws a QR code with half blocks, two modules per terminal line
+     */
+    fn qr_lines(text: &str) -> Vec<Spans<'static>> {
+        let qr = match QrCode::encode_text(text, QrCodeEcc::Low) {
+            Ok(qr) => qr,
+            Err(_) => return vec![],
+        };
+        let border = 1;
+        let style = Style::default().fg(Color::Black).bg(Color::White);
+        let mut lines = vec![];
+        let mut y = -border;
+        while y < qr.size() + border {
+            let line: String = (-border..qr.size() + border)
+                .map(|x| match (qr.get_module(x, y), qr.get_module(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+                .collect();
+            lines.push(Spans::from(Span::styled(line, style)));
+            y += 2;
+        }
+        lines
+    }
+
+    pub fn auth_ui<B: Backend>(f: &mut Frame<B>, auth: &AuthStruct, message: &str) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(60),
+                    Constraint::Percentage(25),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        let hchunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(vchunks[1]);
+
+        /**
+         * ! Top status
+         */
+        let color = match auth.status {
+            AuthStatus::Success => Color::LightGreen,
+            AuthStatus::Failed | AuthStatus::Cancelled => Color::LightRed,
+            _ => Color::Yellow,
+        };
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(color))
+                    .border_type(BorderType::Rounded)
+                    .title(format!(" Adding {} ", auth.name))
+                    .title_alignment(Alignment::Center),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Authorization link
+         */
+        let link = match auth.url.is_empty() {
+            true => vec![Spans::from(Span::styled(
+                "No authorization link yet, rclone may not need one",
+                Style::default().fg(Color::DarkGray),
+            ))],
+            false => vec![
+                Spans::from("Open this link to authorize rclone:"),
+                Spans::from(""),
+                Spans::from(Span::styled(
+                    auth.url.clone(),
+                    Style::default()
+                        .fg(Color::LightCyan)
+                        .add_modifier(Modifier::UNDERLINED),
+                )),
+            ],
+        };
+        let link = Paragraph::new(link)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(" Link ")
+                    .title_alignment(Alignment::Center),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(link, hchunks[0]);
+
+        /**
+         * ! QR code
+         */
+        // a phone cant reach rclone's callback on this machine
+        let loopback = is_loopback(&auth.url);
+        let qr = match auth.url.is_empty() || loopback {
+            true => vec![],
+            false => qr_lines(&auth.url),
+        };
+        let fits = qr
+            .first()
+            .map(|line| line.width() as u16 + 2 <= hchunks[1].width)
+            .unwrap_or(true)
+            && qr.len() as u16 + 2 <= hchunks[1].height;
+        let qr = match (loopback, fits) {
+            (true, _) => vec![Spans::from(Span::styled(
+                "The link only opens in a browser on this machine, no QR code for it",
+                Style::default().fg(Color::DarkGray),
+            ))],
+            (false, true) => qr,
+            (false, false) => vec![Spans::from("Enlarge the terminal to see the QR code")],
+        };
+        let qr = Paragraph::new(qr)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(" QR code ")
+                    .title_alignment(Alignment::Center),
+            )
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false });
+        f.render_widget(qr, hchunks[1]);
+
+        /**
+         * ! rclone output
+         */
+        let height = vchunks[2].height.saturating_sub(2) as usize;
+        let lines: Vec<Spans> = auth
+            .lines
+            .iter()
+            .skip(auth.lines.len().saturating_sub(height))
+            .map(|line| Spans::from(line.clone()))
+            .collect();
+        let output = Paragraph::new(lines).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .title(" rclone | 'c' copy link, 'x' cancel, Esc back to drives ")
+                .title_alignment(Alignment::Center),
+        );
+        f.render_widget(output, vchunks[2]);
+    }
+}
@@ -0,0 +1,138 @@
+#![allow(unused_doc_comments)]
+
+pub mod browser_ui {
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Corner, Direction, Layout},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, List, ListItem},
+        Frame,
+    };
+
+    use crate::lib::{
+        app::app_mod::StatefulList,
+        browser::browser::{FileStruct, SortKey},
+        utils::utils::format_size,
+    };
+
+    /**
+     * Cuts or pads to exactly `width` characters
+     */
+    fn fit(text: &str, width: usize) -> String {
+        let count = text.chars().count();
+        match count > width {
+            true => format!(
+                "{}…",
+                text.chars()
+                    .take(width.saturating_sub(1))
+                    .collect::<String>()
+            ),
+            false => format!("{}{}", text, " ".repeat(width - count)),
+        }
+    }
+
+    pub fn file_item<'a>(file: &FileStruct, width: usize) -> ListItem<'a> {
+        let size = match (file.is_dir, file.size) {
+            (true, _) => String::from("<dir>"),
+            (false, size) if size < 0 => String::from("?"),
+            (false, size) => format_size(size as u64),
+        };
+        let modified = match file.mod_time {
+            Some(time) => time.format("%Y/%m/%d %H:%M").to_string(),
+            None => String::new(),
+        };
+        let name = match file.is_dir {
+            true => format!("{}/", file.name),
+            false => file.name.clone(),
+        };
+        // name takes what the fixed columns leave
+        let name_width = width.saturating_sub(10 + 17 + 20 + 3).max(12);
+        let color = match file.is_dir {
+            true => Color::LightBlue,
+            false => Color::White,
+        };
+        ListItem::new(Spans::from(vec![
+            Span::styled(fit(&name, name_width), Style::default().fg(color)),
+            Span::raw(format!(" {:>10}", size)),
+            Span::styled(
+                format!(" {:>16}", modified),
+                Style::default().fg(Color::DarkGray),
+            ),
+            Span::styled(
+                format!(" {}", fit(&file.mime, 20)),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]))
+    }
+
+    pub fn browser_ui<B: Backend>(
+        f: &mut Frame<B>,
+        files: &StatefulList<FileStruct>,
+        crumbs: &str,
+        loading: bool,
+        sort: SortKey,
+        reverse: bool,
+        message: &str,
+    ) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(15), Constraint::Percentage(85)].as_ref())
+            .split(size);
+
+        /**
+         * ! Breadcrumb and message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded)
+                    .title(Span::styled(
+                        format!(" {} ", crumbs),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ))
+                    .title_alignment(Alignment::Center),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Files block
+         */
+        let width = vchunks[1].width.saturating_sub(5) as usize;
+        let items: Vec<ListItem> = files.items.iter().map(|f| file_item(f, width)).collect();
+        let title = format!(
+            " {} entries{} | sorted by {}{} | 's' sort, 'o' order, 'f' filters, 'u' sizes, 'r' reload, Esc back ",
+            files.items.len(),
+            match loading {
+                true => ", loading",
+                false => "",
+            },
+            sort.label(),
+            match reverse {
+                true => " ↑",
+                false => "",
+            }
+        );
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(title)
+                    .title_alignment(Alignment::Center),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::ITALIC),
+            )
+            .highlight_symbol(">> ")
+            .start_corner(Corner::TopLeft);
+        f.render_stateful_widget(list, vchunks[1], &mut files.state.clone());
+    }
+}
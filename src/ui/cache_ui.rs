@@ -0,0 +1,170 @@
+#![allow(unused_doc_comments)]
+
+pub mod cache_ui {
+    use std::time::SystemTime;
+
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Corner, Direction, Layout},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, List, ListItem, Paragraph},
+        Frame,
+    };
+
+    use crate::lib::{
+        app::app_mod::StatefulList,
+        cache::cache::CacheStruct,
+        utils::utils::{format_duration, format_size},
+    };
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn cache_ui<B: Backend>(
+        f: &mut Frame<B>,
+        caches: &StatefulList<CacheStruct>,
+        mounted: &[String],
+        warn_size: Option<u64>,
+        disk: Option<(u64, u64)>,
+        threshold: &str,
+        insert_mode: bool,
+        message: &str,
+    ) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(70),
+                    Constraint::Percentage(15),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        let hchunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
+            .split(vchunks[1]);
+
+        let bchunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
+            .split(vchunks[2]);
+
+        /**
+         * ! Top message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Caches block
+         */
+        let items: Vec<ListItem> = caches
+            .items
+            .iter()
+            .map(|c| {
+                let color = match warn_size {
+                    Some(warn) if c.size > warn => Color::LightRed,
+                    _ => Color::White,
+                };
+                ListItem::new(Spans::from(vec![
+                    Span::styled(c.name.clone(), Style::default().fg(color)),
+                    Span::styled(
+                        format!(" {}", format_size(c.size)),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect();
+
+        let list_caches = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" VFS caches ")
+                    .title_alignment(Alignment::Center),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::ITALIC),
+            )
+            .highlight_symbol(">> ")
+            .start_corner(Corner::TopLeft);
+        f.render_stateful_widget(list_caches, hchunks[0], &mut caches.state.clone());
+
+        /**
+         * ! Details block
+         */
+        let details: Vec<Spans> = match caches.state.selected().and_then(|i| caches.items.get(i)) {
+            Some(cache) => {
+                let age = match cache.modified {
+                    Some(modified) => match SystemTime::now().duration_since(modified) {
+                        Ok(age) => format!("{} ago", format_duration(age.as_secs())),
+                        Err(_) => String::from("just now"),
+                    },
+                    None => String::from("empty"),
+                };
+                let state = match mounted.contains(&cache.name) {
+                    true => Span::styled("mounted", Style::default().fg(Color::LightGreen)),
+                    false => Span::styled("not mounted", Style::default().fg(Color::DarkGray)),
+                };
+                vec![
+                    Spans::from(format!("Path:      {}", cache.path.to_string_lossy())),
+                    Spans::from(format!("Size:      {}", format_size(cache.size))),
+                    Spans::from(format!("Files:     {}", cache.files)),
+                    Spans::from(format!("Last used: {}", age)),
+                    Spans::from(vec![Span::raw("State:     "), state]),
+                ]
+            }
+            None => vec![Spans::from("No VFS cache found")],
+        };
+        let details = Paragraph::new(details).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Details ")
+                .title_alignment(Alignment::Center),
+        );
+        f.render_widget(details, hchunks[1]);
+
+        /**
+         * ! Disk and threshold
+         */
+        let disk = match disk {
+            Some((total, available)) => format!(
+                "Disk: {} free of {} | 'x' purge, 'r' rescan, 't' threshold, 'm' main",
+                format_size(available),
+                format_size(total)
+            ),
+            None => String::from("'x' purge, 'r' rescan, 't' threshold, 'm' main"),
+        };
+        let gauge = Gauge::default()
+            .label(Span::styled(disk, Style::default().fg(Color::DarkGray)))
+            .style(Style::default());
+        f.render_widget(gauge, bchunks[0]);
+
+        let input = Paragraph::new(threshold.to_owned()).block(
+            Block::default()
+                .title(" Warn above -> Enter ")
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(match insert_mode {
+                    true => Style::default().fg(Color::Green),
+                    false => Style::default(),
+                }),
+        );
+        f.render_widget(input, bchunks[1]);
+    }
+}
@@ -0,0 +1,186 @@
+#![allow(unused_doc_comments)]
+
+pub mod commander_ui {
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Corner, Direction, Layout, Rect},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, List, ListItem, Paragraph},
+        Frame,
+    };
+
+    use crate::lib::{
+        commander::commander::PaneStruct,
+        jobs::jobs::{JobState, JobStruct},
+        utils::utils::format_size,
+    };
+
+    fn pane_ui<B: Backend>(f: &mut Frame<B>, pane: &PaneStruct, area: Rect, active: bool) {
+        let width = area.width.saturating_sub(5) as usize;
+        let items: Vec<ListItem> = pane
+            .files
+            .items
+            .iter()
+            .map(|file| {
+                let (name, size, color) = match file.is_dir {
+                    true => (format!("{}/", file.name), String::new(), Color::LightBlue),
+                    false => (
+                        file.name.clone(),
+                        format_size(file.size.max(0) as u64),
+                        Color::White,
+                    ),
+                };
+                let name_width = width.saturating_sub(11);
+                let name: String = match name.chars().count() > name_width {
+                    true => format!(
+                        "{}…",
+                        name.chars()
+                            .take(name_width.saturating_sub(1))
+                            .collect::<String>()
+                    ),
+                    false => name,
+                };
+                ListItem::new(Spans::from(vec![
+                    Span::styled(
+                        format!("{:<w$}", name, w = name_width),
+                        Style::default().fg(color),
+                    ),
+                    Span::styled(
+                        format!(" {:>10}", size),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                ]))
+            })
+            .collect();
+        let mut title = format!(" {} ", pane.title());
+        if pane.loading() {
+            title.push_str("(loading) ");
+        }
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(match active {
+                        true => Style::default().fg(Color::Green),
+                        false => Style::default(),
+                    })
+                    .title(title)
+                    .title_alignment(Alignment::Center),
+            )
+            .highlight_style(match active {
+                true => Style::default()
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::ITALIC),
+                false => Style::default().add_modifier(Modifier::REVERSED),
+            })
+            .start_corner(Corner::TopLeft);
+        f.render_stateful_widget(list, area, &mut pane.files.state.clone());
+    }
+
+    pub fn commander_ui<B: Backend>(
+        f: &mut Frame<B>,
+        left: &PaneStruct,
+        right: &PaneStruct,
+        active: usize,
+        jobs: &[JobStruct],
+        input: Option<(&str, &str)>,
+        message: &str,
+    ) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(70),
+                    Constraint::Percentage(15),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        let hchunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(vchunks[1]);
+
+        /**
+         * ! Top message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded)
+                    .title(
+                        " Tab switch | c copy, m move, x delete, n mkdir, e rename, s source, l reload ",
+                    )
+                    .title_alignment(Alignment::Center),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Panes
+         */
+        pane_ui(f, left, hchunks[0], active == 0);
+        pane_ui(f, right, hchunks[1], active == 1);
+
+        /**
+         * ! Name input or latest job
+         */
+        if let Some((label, value)) = input {
+            let input = Paragraph::new(value.to_owned()).block(
+                Block::default()
+                    .title(format!(" {} -> Enter | Esc cancel ", label))
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Green)),
+            );
+            f.render_widget(input, vchunks[2]);
+            return;
+        }
+        let running = jobs.iter().filter(|j| j.state == JobState::Running).count();
+        let (label, ratio, color) = match jobs
+            .iter()
+            .rev()
+            .find(|j| j.state == JobState::Running)
+            .or(jobs.last())
+        {
+            Some(job) => (
+                format!(
+                    "#{} {} | {} | {}",
+                    job.id,
+                    job.label,
+                    job.state.label(),
+                    job.progress()
+                ),
+                job.ratio(),
+                match job.state {
+                    JobState::Done => Color::LightGreen,
+                    JobState::Failed | JobState::Cancelled => Color::LightRed,
+                    _ => Color::Yellow,
+                },
+            ),
+            None => (String::from("No transfers yet"), 0.0, Color::DarkGray),
+        };
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(format!(" Jobs, {} running ", running))
+                    .title_alignment(Alignment::Center),
+            )
+            .gauge_style(Style::default().fg(color))
+            .ratio(ratio)
+            .label(Span::styled(label, Style::default()));
+        f.render_widget(gauge, vchunks[2]);
+    }
+}
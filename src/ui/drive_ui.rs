@@ -11,14 +11,81 @@ pub mod drive_ui {
         Frame,
     };
 
-    use crate::lib::{app::app_mod::StatefulList, config::config::DriveStruct};
+    use crate::lib::{
+        app::app_mod::StatefulList,
+        config::config::{layering, DriveStruct, SettingsStruct},
+        mount::mount::MountStatusStruct,
+        quota::quota::{QuotaCacheStruct, QuotaStruct},
+        utils::utils::format_size,
+    };
+    use crate::ui::main_ui::main_ui::drive_item;
+
+    /**
+     * Total, used, trashed and free space as one gauge each
+     */
+    fn quota_gauges<B: Backend>(f: &mut Frame<B>, area: Rect, quota: &QuotaStruct, warn: u8) {
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(1); 4].as_ref())
+            .split(area);
+        let total = quota.total.filter(|t| *t > 0);
+        let ratio = |part: Option<u64>| match (part, total) {
+            (Some(part), Some(total)) => (part as f64 / total as f64).min(1.0),
+            _ => 0.0,
+        };
+        let size = |part: Option<u64>| part.map_or(String::from("unknown"), format_size);
+        let used_color = match quota.is_full(warn) {
+            true => Color::LightRed,
+            false => Color::LightGreen,
+        };
+        let gauges = [
+            (
+                format!(
+                    "total {}{}",
+                    size(quota.total),
+                    quota
+                        .percent()
+                        .map_or(String::new(), |p| format!(", {}% taken", p))
+                ),
+                quota.used_ratio().unwrap_or(0.0),
+                used_color,
+            ),
+            (
+                format!("used {}", size(quota.used)),
+                ratio(quota.used),
+                used_color,
+            ),
+            (
+                format!("trashed {}", size(quota.trashed)),
+                ratio(quota.trashed),
+                Color::Yellow,
+            ),
+            (
+                format!("free {}", size(quota.free)),
+                ratio(quota.free),
+                Color::LightBlue,
+            ),
+        ];
+        for (index, (label, ratio, color)) in gauges.into_iter().enumerate() {
+            let gauge = Gauge::default()
+                .gauge_style(Style::default().fg(color).bg(Color::DarkGray))
+                .ratio(ratio)
+                .label(Span::styled(label, Style::default().fg(Color::White)));
+            f.render_widget(gauge, rows[index]);
+        }
+    }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn drive_ui<B: Backend>(
         f: &mut Frame<B>,
         drives: &StatefulList<DriveStruct>,
+        statuses: &[MountStatusStruct],
         message: &str,
+        settings: &SettingsStruct,
         name: String,
+        name_error: Option<&str>,
         inser_mode: bool,
+        quotas: &QuotaCacheStruct,
     ) {
         let size = f.size();
 
@@ -57,16 +124,21 @@ pub mod drive_ui {
         let items: Vec<ListItem> = drives
             .items
             .iter()
-            .map(|i| {
-                ListItem::new(Spans::from(i.name.clone())).style(Style::default().fg(Color::White))
-            })
+            .enumerate()
+            .map(|(index, drive)| drive_item(drive, statuses.get(index), settings, quotas))
             .collect();
 
         let list_drives = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title(" Drives list ")
+                    .title(match settings.safe_mode {
+                        true => Span::styled(
+                            " Drives list | SAFE MODE ",
+                            Style::default().fg(Color::LightRed),
+                        ),
+                        false => Span::raw(" Drives list "),
+                    })
                     .title_alignment(Alignment::Center),
             )
             .highlight_style(
@@ -82,13 +154,20 @@ pub mod drive_ui {
         let input = Paragraph::new(name.to_owned())
             .block(
                 Block::default()
-                    .title(" Add drive -> Enter ")
+                    .title(match name_error {
+                        Some(error) => Span::styled(
+                            format!(" ⚠ {} ", error),
+                            Style::default().fg(Color::LightRed),
+                        ),
+                        None => Span::raw(" Add drive -> Enter "),
+                    })
                     .title_alignment(Alignment::Center)
                     .borders(Borders::ALL)
                     .border_type(BorderType::Rounded)
-                    .border_style(match inser_mode {
-                        true => Style::default().fg(Color::Green),
-                        false => Style::default(),
+                    .border_style(match (name_error, inser_mode) {
+                        (Some(_), _) => Style::default().fg(Color::LightRed),
+                        (None, true) => Style::default().fg(Color::Green),
+                        (None, false) => Style::default(),
                     }),
             )
             .style(Style::default());
@@ -98,9 +177,11 @@ pub mod drive_ui {
             .direction(Direction::Vertical)
             .constraints(
                 [
-                    Constraint::Percentage(33),
-                    Constraint::Percentage(33),
-                    Constraint::Percentage(33),
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(25),
+                    Constraint::Percentage(30),
                 ]
                 .as_ref(),
             )
@@ -130,7 +211,29 @@ pub mod drive_ui {
             .style(Style::default());
         f.render_widget(gauge, rvchunks[2][0]);
 
-        let i = drives.state.selected().unwrap();
+        let gauge = Gauge::default()
+            .label(Span::styled(
+                format!("Quota, warn at {}% (+/-):", settings.quota_warn),
+                Style::default(),
+            ))
+            .style(Style::default());
+        f.render_widget(gauge, rvchunks[4][0]);
+
+        let i = match drives.state.selected() {
+            Some(i) if i < drives.items.len() => i,
+            _ => return,
+        };
+
+        let gauge = Gauge::default()
+            .label(Span::styled(
+                match drives.items[i].upstreams().is_empty() {
+                    true => "Layers:",
+                    false => "Upstreams:",
+                },
+                Style::default(),
+            ))
+            .style(Style::default());
+        f.render_widget(gauge, rvchunks[3][0]);
 
         let gauge = Gauge::default()
             .label(Span::styled(drives.items[i].name.clone(), Style::default()))
@@ -145,16 +248,84 @@ pub mod drive_ui {
             .style(Style::default());
         f.render_widget(gauge, rvchunks[1][1]);
 
-        let mut expiry = drives.items[i]
-            .token
-            .expiry
-            .clone()
-            .to_rfc3339_opts(SecondsFormat::AutoSi, false);
-        expiry = expiry[0..16].replace("T", " ").replace("-", "/");
+        let expiry = match &drives.items[i].token {
+            Some(token) => {
+                let expiry = token.expiry.to_rfc3339_opts(SecondsFormat::AutoSi, false);
+                expiry[0..16].replace('T', " ").replace('-', "/")
+            }
+            None if drives.items[i].is_service_account() => String::from("service account"),
+            None => String::from("no token"),
+        };
         let gauge = Gauge::default()
             .label(Span::styled(&expiry, Style::default()))
             .style(Style::default());
         f.render_widget(gauge, rvchunks[2][1]);
+
+        /**
+         * ! Quota from rclone about
+         */
+        let name = &drives.items[i].name;
+        match quotas.get(name) {
+            Some(entry) => match &entry.quota {
+                Ok(quota) => quota_gauges(f, rvchunks[4][1], quota, settings.quota_warn),
+                Err(e) => {
+                    let gauge = Gauge::default()
+                        .label(Span::styled(
+                            format!("no quota, {}", e),
+                            Style::default().fg(Color::DarkGray),
+                        ))
+                        .style(Style::default());
+                    f.render_widget(gauge, rvchunks[4][1]);
+                }
+            },
+            None => {
+                let gauge = Gauge::default()
+                    .label(Span::styled(
+                        match quotas.is_fetching(name) {
+                            true => "asking rclone about ...",
+                            false => "unknown, 'a' to fetch",
+                        },
+                        Style::default().fg(Color::DarkGray),
+                    ))
+                    .style(Style::default());
+                f.render_widget(gauge, rvchunks[4][1]);
+            }
+        }
+
+        let upstreams = drives.items[i].upstreams();
+        if upstreams.is_empty() {
+            let layers =
+                layering(&drives.items, &drives.items[i]).unwrap_or(String::from("direct"));
+            let gauge = Gauge::default()
+                .label(Span::styled(&layers, Style::default()))
+                .style(Style::default());
+            f.render_widget(gauge, rvchunks[3][1]);
+        } else {
+            /**
+             * ! Upstreams tree of union and combine remotes
+             */
+            let mut tree = vec![Spans::from(drives.items[i].drive_type.clone())];
+            for (index, upstream) in upstreams.iter().enumerate() {
+                let branch = match index + 1 == upstreams.len() {
+                    true => "└─ ",
+                    false => "├─ ",
+                };
+                // combine upstreams are dir=remote:path
+                let remote = upstream
+                    .split_once('=')
+                    .map_or(upstream.as_str(), |(_, r)| r);
+                let name = remote.split_once(':').map_or(remote, |(name, _)| name);
+                let mut spans = vec![Span::raw(branch), Span::raw(upstream.clone())];
+                if let Some(drive) = drives.items.iter().find(|d| d.name == name) {
+                    spans.push(Span::styled(
+                        format!(" ({})", drive.drive_type),
+                        Style::default().fg(Color::DarkGray),
+                    ));
+                }
+                tree.push(Spans::from(spans));
+            }
+            f.render_widget(Paragraph::new(tree), rvchunks[3][1]);
+        }
     }
 
     // fn inactivate(textarea: &mut TextArea<'_>) {
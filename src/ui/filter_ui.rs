@@ -0,0 +1,203 @@
+#![allow(unused_doc_comments)]
+
+pub mod filter_ui {
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Corner, Direction, Layout},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, List, ListItem, Paragraph, Wrap},
+        Frame,
+    };
+
+    use crate::{
+        lib::filter::filter::{rule_problem, FilterEditorStruct, FilterInput},
+        ui::form_ui::form_ui::form_ui,
+    };
+
+    pub fn filter_ui<B: Backend>(f: &mut Frame<B>, editor: &FilterEditorStruct, message: &str) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(70),
+                    Constraint::Percentage(15),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        let hchunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)].as_ref())
+            .split(vchunks[1]);
+
+        let lchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(55), Constraint::Percentage(45)].as_ref())
+            .split(hchunks[0]);
+
+        /**
+         * ! Top message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded)
+                    .title(format!(" {} ", editor.title()))
+                    .title_alignment(Alignment::Center),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Rules, first match wins
+         */
+        let items: Vec<ListItem> = editor
+            .rules
+            .items
+            .iter()
+            .map(|rule| {
+                let color = match (rule_problem(rule), rule.chars().next()) {
+                    (Some(_), _) => Color::LightRed,
+                    (None, Some('+')) => Color::LightGreen,
+                    (None, Some('-')) => Color::Yellow,
+                    _ => Color::LightBlue,
+                };
+                ListItem::new(Span::styled(rule.clone(), Style::default().fg(color)))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(match editor.focus_rules {
+                        true => Style::default().fg(Color::Green),
+                        false => Style::default(),
+                    })
+                    .title(" Rules, the first match wins ")
+                    .title_alignment(Alignment::Center),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::ITALIC),
+            )
+            .start_corner(Corner::TopLeft);
+        f.render_stateful_widget(list, lchunks[0], &mut editor.rules.state.clone());
+
+        /**
+         * ! Size, age and rule file
+         */
+        let title = match editor.focus_rules {
+            true => " Limits (Tab) ",
+            false => " Limits, editing ",
+        };
+        form_ui(f, lchunks[1], &editor.form, title);
+
+        /**
+         * ! Preview
+         */
+        let (title, items) = match &editor.preview {
+            Some(preview) => {
+                let mut files: Vec<_> = preview.files.iter().collect();
+                files.sort_by(|a, b| a.path.cmp(&b.path));
+                let kept = files
+                    .iter()
+                    .filter(|f| preview.matched.contains(&f.path))
+                    .count();
+                let items: Vec<ListItem> = files
+                    .iter()
+                    .map(|file| {
+                        let name = match file.is_dir {
+                            true => format!("{}/", file.path),
+                            false => file.path.clone(),
+                        };
+                        match preview.matched.contains(&file.path) {
+                            true => ListItem::new(Span::styled(
+                                format!("✔ {}", name),
+                                Style::default().fg(Color::LightGreen),
+                            )),
+                            false => ListItem::new(Span::styled(
+                                format!("✘ {}", name),
+                                Style::default().fg(Color::DarkGray),
+                            )),
+                        }
+                    })
+                    .collect();
+                (
+                    format!(
+                        " {}: {} of {} kept{} ",
+                        preview.target,
+                        kept,
+                        files.len(),
+                        match (preview.done(), preview.failed()) {
+                            (false, _) => ", listing…",
+                            (true, true) => ", listing failed",
+                            _ => "",
+                        }
+                    ),
+                    items,
+                )
+            }
+            None => (format!(" Preview of {} ", editor.preview_dir), vec![]),
+        };
+        let list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_alignment(Alignment::Center),
+        );
+        f.render_widget(list, hchunks[1]);
+
+        /**
+         * ! Input, problems or keys
+         */
+        let problems = editor.problems();
+        let (title, lines, color) = match editor.input {
+            Some(input) => (
+                match input {
+                    FilterInput::Include => " Include pattern -> Enter | Esc cancel ",
+                    FilterInput::Exclude => " Exclude pattern -> Enter | Esc cancel ",
+                    FilterInput::Edit(_) => " Rule -> Enter | Esc cancel ",
+                    FilterInput::PreviewDir => " Folder to preview, local or remote:path -> Enter ",
+                },
+                vec![Spans::from(editor.input_text.clone())],
+                Color::Green,
+            ),
+            None if !problems.is_empty() => (
+                " Problems ",
+                problems
+                    .iter()
+                    .map(|p| Spans::from(format!("⚠ {}", p)))
+                    .collect(),
+                Color::LightRed,
+            ),
+            None => (
+                " Keys ",
+                vec![Spans::from(match editor.focus_rules {
+                    true => "+ include | - exclude | Enter edit | x delete | K/J move | ! reset | p preview folder | r refresh | Tab limits | s save | Esc back",
+                    false => "Up/Down field | type to edit | Ctrl+R refresh | Tab rules | Ctrl+S save | Esc back",
+                })],
+                Color::DarkGray,
+            ),
+        };
+        let bottom = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(color)),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(bottom, vchunks[2]);
+    }
+}
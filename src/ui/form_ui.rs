@@ -0,0 +1,120 @@
+#![allow(unused_doc_comments)]
+
+pub mod form_ui {
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Direction, Layout, Rect},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, List, ListItem, ListState, Paragraph, Wrap},
+        Frame,
+    };
+
+    use crate::lib::form::form::FormStruct;
+
+    /**
+     * Renders a form as a list of `label: value` lines inside a block
+     */
+    pub fn form_ui<B: Backend>(f: &mut Frame<B>, area: Rect, form: &FormStruct, title: &str) {
+        let width = form
+            .fields
+            .iter()
+            .map(|field| field.label.chars().count())
+            .max()
+            .unwrap_or(0);
+
+        let items: Vec<ListItem> = form
+            .fields
+            .iter()
+            .map(|field| {
+                ListItem::new(Spans::from(vec![
+                    Span::styled(
+                        format!("{:width$} : ", field.label, width = width),
+                        Style::default().fg(Color::LightCyan),
+                    ),
+                    Span::styled(field.display(), Style::default().fg(Color::White)),
+                ]))
+            })
+            .collect();
+
+        let mut state = ListState::default();
+        state.select(Some(form.selected));
+
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Green))
+                    .title(title.to_owned())
+                    .title_alignment(Alignment::Center),
+            )
+            .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+            .highlight_symbol(">> ");
+        f.render_stateful_widget(list, area, &mut state);
+    }
+
+    /**
+     * Full screen form with a message on top and help lines below
+     */
+    pub fn form_screen_ui<B: Backend>(
+        f: &mut Frame<B>,
+        form: &FormStruct,
+        title: &str,
+        message: &str,
+        help: &[String],
+    ) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(55),
+                    Constraint::Percentage(30),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        /**
+         * ! Top message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        form_ui(f, vchunks[1], form, title);
+
+        /**
+         * ! Help
+         */
+        let lines: Vec<Spans> = help
+            .iter()
+            .map(|line| {
+                Spans::from(Span::styled(
+                    line.clone(),
+                    Style::default().fg(Color::DarkGray),
+                ))
+            })
+            .collect();
+        let help = Paragraph::new(lines)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(" Help ")
+                    .title_alignment(Alignment::Center),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(help, vchunks[2]);
+    }
+}
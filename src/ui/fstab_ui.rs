@@ -0,0 +1,73 @@
+#![allow(unused_doc_comments)]
+
+pub mod fstab_ui {
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Direction, Layout},
+        style::{Color, Style},
+        text::Span,
+        widgets::{Block, BorderType, Borders, Gauge, Paragraph, Wrap},
+        Frame,
+    };
+
+    pub fn fstab_ui<B: Backend>(f: &mut Frame<B>, entries: &[String], message: &str) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(35),
+                    Constraint::Percentage(35),
+                    Constraint::Percentage(15),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        /**
+         * ! Top message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Entries blocks
+         */
+        let titles = [" /etc/fstab ", " autofs map "];
+        for (index, title) in titles.iter().enumerate() {
+            let entry = Paragraph::new(entries.get(index).cloned().unwrap_or_default())
+                .block(
+                    Block::default()
+                        .title(*title)
+                        .title_alignment(Alignment::Center)
+                        .borders(Borders::ALL)
+                        .border_type(BorderType::Rounded)
+                        .border_style(Style::default().fg(Color::LightCyan)),
+                )
+                .wrap(Wrap { trim: false })
+                .style(Style::default());
+            f.render_widget(entry, vchunks[index + 1]);
+        }
+
+        /**
+         * ! Keys
+         */
+        let gauge = Gauge::default()
+            .label(Span::styled(
+                "'w' write to files | 'm' back to main",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[3]);
+    }
+}
@@ -0,0 +1,102 @@
+#![allow(unused_doc_comments)]
+
+pub mod headless_ui {
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Direction, Layout},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, Paragraph, Wrap},
+        Frame,
+    };
+
+    use crate::lib::auth::auth::{authorize_command, HeadlessStruct};
+
+    pub fn headless_ui<B: Backend>(f: &mut Frame<B>, headless: &HeadlessStruct, message: &str) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(45),
+                    Constraint::Percentage(40),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        /**
+         * ! Top message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded)
+                    .title(format!(" Adding {} ", headless.name))
+                    .title_alignment(Alignment::Center),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Instructions
+         */
+        let steps = vec![
+            Spans::from("1. On a machine with a browser and rclone, run:"),
+            Spans::from(""),
+            Spans::from(Span::styled(
+                format!(
+                    "   {}",
+                    authorize_command(&headless.backend, &headless.params)
+                ),
+                Style::default()
+                    .fg(Color::LightCyan)
+                    .add_modifier(Modifier::BOLD),
+            )),
+            Spans::from(""),
+            Spans::from("2. Log in, then copy the JSON token rclone prints"),
+            Spans::from("3. Paste it below and press Enter"),
+        ];
+        let steps = Paragraph::new(steps)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(" Remote authorization ")
+                    .title_alignment(Alignment::Center),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(steps, vchunks[1]);
+
+        /**
+         * ! Token input, only the start of the secret is shown
+         */
+        let token = match headless.token.chars().count() {
+            0 => Span::styled(
+                "Waiting for paste ...",
+                Style::default().fg(Color::DarkGray),
+            ),
+            count => Span::raw(format!(
+                "{}... ({} characters)",
+                headless.token.chars().take(24).collect::<String>(),
+                count
+            )),
+        };
+        let input = Paragraph::new(Spans::from(token))
+            .block(
+                Block::default()
+                    .title(" Token -> Enter | Backspace clear | Esc cancel ")
+                    .title_alignment(Alignment::Center)
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Green)),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(input, vchunks[2]);
+    }
+}
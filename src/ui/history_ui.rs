@@ -0,0 +1,168 @@
+#![allow(unused_doc_comments)]
+
+pub mod history_ui {
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Corner, Direction, Layout},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, List, ListItem, ListState, Paragraph},
+        Frame,
+    };
+
+    use crate::lib::{
+        history::history::HistoryEntryStruct,
+        schedule::schedule::format_time,
+        utils::utils::{format_duration, format_size},
+    };
+
+    fn state_color(state: &str) -> Color {
+        match state {
+            "done" => Color::LightGreen,
+            "cancelled" => Color::Yellow,
+            _ => Color::LightRed,
+        }
+    }
+
+    fn entry_item<'a>(entry: &HistoryEntryStruct) -> ListItem<'a> {
+        ListItem::new(Spans::from(vec![
+            Span::styled(
+                format!("{:<10}", entry.state),
+                Style::default().fg(state_color(&entry.state)),
+            ),
+            Span::raw(format!(
+                "{}  {:>8}  {:>10}  {:>5} files  ",
+                format_time(entry.started),
+                format_duration(entry.duration()),
+                format_size(entry.bytes),
+                entry.files
+            )),
+            Span::styled(
+                entry.label.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            ),
+        ]))
+    }
+
+    pub fn history_ui<B: Backend>(
+        f: &mut Frame<B>,
+        entries: &[&HistoryEntryStruct],
+        state: &ListState,
+        filter: Option<&str>,
+        message: &str,
+    ) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(70),
+                    Constraint::Percentage(15),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        /**
+         * ! Top message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded)
+                    .title(" / filter | Enter log | J json report | M markdown report | Esc back ")
+                    .title_alignment(Alignment::Center),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! History block
+         */
+        let items: Vec<ListItem> = entries.iter().map(|e| entry_item(e)).collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(" {} jobs ", entries.len()))
+                    .title_alignment(Alignment::Center),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::ITALIC),
+            )
+            .start_corner(Corner::TopLeft);
+        f.render_stateful_widget(list, vchunks[1], &mut state.clone());
+
+        /**
+         * ! Filter or selected job
+         */
+        let (title, text, color) = match (filter, state.selected().and_then(|i| entries.get(i))) {
+            (Some(filter), _) => (
+                String::from(" Filter by name, state or date -> Enter | Esc clear "),
+                filter.to_owned(),
+                Color::Green,
+            ),
+            (None, Some(entry)) => (
+                format!(
+                    " exit {} | {} deletes | {} errors ",
+                    entry.exit, entry.deletes, entry.errors
+                ),
+                match entry.error.is_empty() {
+                    true => format!("rclone {}", entry.args.join(" ")),
+                    false => entry.error.clone(),
+                },
+                state_color(&entry.state),
+            ),
+            (None, None) => (
+                String::new(),
+                String::from("No jobs recorded"),
+                Color::DarkGray,
+            ),
+        };
+        let bottom = Paragraph::new(text).block(
+            Block::default()
+                .title(title)
+                .title_alignment(Alignment::Center)
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(color)),
+        );
+        f.render_widget(bottom, vchunks[2]);
+    }
+
+    pub fn log_ui<B: Backend>(f: &mut Frame<B>, title: &str, lines: &[String], scroll: u16) {
+        let text: Vec<Spans> = lines
+            .iter()
+            .map(|line| {
+                let color = match line {
+                    l if l.contains("\"level\":\"error\"") || l.contains("ERROR") => {
+                        Color::LightRed
+                    }
+                    l if l.contains("\"level\":\"warning\"") => Color::Yellow,
+                    _ => Color::White,
+                };
+                Spans::from(Span::styled(line.clone(), Style::default().fg(color)))
+            })
+            .collect();
+        let log = Paragraph::new(text)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(format!(
+                        " {} | Up/Down PageUp/PageDown scroll, Esc back ",
+                        title
+                    ))
+                    .title_alignment(Alignment::Center),
+            )
+            .scroll((scroll, 0));
+        f.render_widget(log, f.size());
+    }
+}
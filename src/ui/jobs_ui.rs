@@ -0,0 +1,150 @@
+#![allow(unused_doc_comments)]
+
+pub mod jobs_ui {
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Corner, Direction, Layout},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, List, ListItem, ListState},
+        Frame,
+    };
+
+    use crate::lib::jobs::jobs::{JobState, JobStruct};
+
+    fn state_color(state: JobState) -> Color {
+        match state {
+            JobState::Queued => Color::DarkGray,
+            JobState::Running => Color::Yellow,
+            JobState::Done => Color::LightGreen,
+            JobState::Failed | JobState::Cancelled => Color::LightRed,
+        }
+    }
+
+    /**
+     * `[#####.....]` bar, a gauge per row doesnt fit a list
+     */
+    fn bar(ratio: f64, width: usize) -> String {
+        let done = (ratio * width as f64).round() as usize;
+        format!(
+            "[{}{}]",
+            "#".repeat(done),
+            ".".repeat(width - done.min(width))
+        )
+    }
+
+    fn job_item<'a>(job: &JobStruct) -> ListItem<'a> {
+        let color = state_color(job.state);
+        ListItem::new(Spans::from(vec![
+            Span::styled(
+                format!("#{:<4} {:<10}", job.id, job.state.label()),
+                Style::default().fg(color),
+            ),
+            Span::styled(
+                format!("{} ", bar(job.ratio(), 20)),
+                Style::default().fg(color),
+            ),
+            Span::raw(job.label.clone()),
+            Span::styled(
+                format!("  {}", job.progress()),
+                Style::default().fg(Color::DarkGray),
+            ),
+        ]))
+    }
+
+    pub fn jobs_ui<B: Backend>(
+        f: &mut Frame<B>,
+        jobs: &[JobStruct],
+        state: &ListState,
+        max_jobs: usize,
+        message: &str,
+    ) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(70),
+                    Constraint::Percentage(15),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        /**
+         * ! Top message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded)
+                    .title(" x cancel, r retry, c clear finished, +/- parallel jobs, h history, Esc back ")
+                    .title_alignment(Alignment::Center),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Jobs block
+         */
+        let running = jobs.iter().filter(|j| j.state == JobState::Running).count();
+        let queued = jobs.iter().filter(|j| j.state == JobState::Queued).count();
+        let items: Vec<ListItem> = jobs.iter().map(job_item).collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(
+                        " {} running of {} at most, {} queued ",
+                        running, max_jobs, queued
+                    ))
+                    .title_alignment(Alignment::Center),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::ITALIC),
+            )
+            .start_corner(Corner::TopLeft);
+        f.render_stateful_widget(list, vchunks[1], &mut state.clone());
+
+        /**
+         * ! Selected job
+         */
+        let job = state.selected().and_then(|i| jobs.get(i));
+        let (title, label, ratio, color) = match job {
+            Some(job) => (
+                format!(" rclone {} ", job.args.join(" ")),
+                match job.error.is_empty() {
+                    true => format!("{} | {}", job.state.label(), job.progress()),
+                    false => format!("{} | {}", job.state.label(), job.error),
+                },
+                job.ratio(),
+                state_color(job.state),
+            ),
+            None => (
+                String::new(),
+                String::from("No jobs yet"),
+                0.0,
+                Color::DarkGray,
+            ),
+        };
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(title)
+                    .title_alignment(Alignment::Center),
+            )
+            .gauge_style(Style::default().fg(color))
+            .ratio(ratio)
+            .label(Span::styled(label, Style::default()));
+        f.render_widget(gauge, vchunks[2]);
+    }
+}
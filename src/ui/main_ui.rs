@@ -11,12 +11,90 @@ pub mod main_ui {
     };
     use tui_logger::{TuiLoggerLevelOutput, TuiLoggerWidget};
 
-    use crate::lib::{app::app_mod::StatefulList, config::config::DriveStruct};
+    use crate::lib::{
+        app::app_mod::StatefulList,
+        config::config::{DriveStruct, SettingsStruct, TokenHealth},
+        mount::mount::{MountState, MountStatusStruct},
+        quota::quota::QuotaCacheStruct,
+    };
+
+    fn backend_icon(drive_type: &str) -> &'static str {
+        match drive_type {
+            "crypt" => "🔐",
+            "union" | "combine" => "🧩",
+            "alias" => "🔗",
+            "local" => "💻",
+            "s3" | "b2" | "swift" | "azureblob" | "gcs" => "🪣",
+            "sftp" | "ftp" | "smb" | "webdav" => "🖥",
+            _ => "☁",
+        }
+    }
+
+    /**
+     * One remote with its backend, mount state and point, read-only badge, token health
+     * and a warning when it is almost full
+     */
+    pub fn drive_item<'a>(
+        drive: &DriveStruct,
+        status: Option<&MountStatusStruct>,
+        settings: &SettingsStruct,
+        quotas: &QuotaCacheStruct,
+    ) -> ListItem<'a> {
+        let state = status.map_or(MountState::Unmounted, |s| s.state);
+        let color = match state {
+            MountState::Mounted => Color::LightGreen,
+            MountState::Mounting => Color::Yellow,
+            MountState::Failed => Color::LightRed,
+            MountState::Serving => Color::LightBlue,
+            MountState::Unmounted => Color::White,
+        };
+        let mut spans = vec![
+            Span::raw(format!("{} ", backend_icon(&drive.drive_type))),
+            Span::styled(drive.name.clone(), Style::default().fg(color)),
+        ];
+        if state != MountState::Unmounted {
+            spans.push(Span::styled(
+                format!(" {}", state.label()),
+                Style::default().fg(color),
+            ));
+        }
+        if let Some(status) = status.filter(|s| !s.point.is_empty()) {
+            spans.push(Span::styled(
+                format!(" {}", status.point),
+                Style::default().fg(Color::DarkGray),
+            ));
+        }
+        if settings.mount_options(&drive.name).read_only {
+            spans.push(Span::styled(" [ro]", Style::default().fg(Color::Yellow)));
+        }
+        let token = match drive.token_health() {
+            TokenHealth::Valid => Some(Color::LightGreen),
+            TokenHealth::Refreshable => Some(Color::Yellow),
+            TokenHealth::Expired | TokenHealth::Missing => Some(Color::LightRed),
+            TokenHealth::NotNeeded => None,
+        };
+        if let Some(token) = token {
+            spans.push(Span::styled(" ●", Style::default().fg(token)));
+        }
+        if let Some(quota) = quotas
+            .quota(&drive.name)
+            .filter(|q| q.is_full(settings.quota_warn))
+        {
+            spans.push(Span::styled(
+                format!(" ⚠ {}%", quota.percent().unwrap_or(100)),
+                Style::default().fg(Color::LightRed),
+            ));
+        }
+        ListItem::new(Spans::from(spans))
+    }
 
     pub fn main_ui<B: Backend>(
         f: &mut Frame<B>,
         drives: &StatefulList<DriveStruct>,
+        statuses: &[MountStatusStruct],
         message: &str,
+        settings: &SettingsStruct,
+        quotas: &QuotaCacheStruct,
     ) {
         let size = f.size();
 
@@ -67,16 +145,20 @@ pub mod main_ui {
         let items: Vec<ListItem> = drives
             .items
             .iter()
-            .map(|i| {
-                ListItem::new(Spans::from(i.name.clone())).style(Style::default().fg(Color::White))
-            })
+            .enumerate()
+            .map(|(index, drive)| drive_item(drive, statuses.get(index), settings, quotas))
             .collect();
 
         let list_drives = List::new(items)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
-                    .title("List")
+                    .title(match settings.safe_mode {
+                        true => {
+                            Span::styled("List | SAFE MODE", Style::default().fg(Color::LightRed))
+                        }
+                        false => Span::raw("List"),
+                    })
                     .title_alignment(Alignment::Center),
             )
             .highlight_style(
@@ -91,6 +173,10 @@ pub mod main_ui {
         /**
          * ! Bottom message
          */
+        let message = match settings.safe_mode {
+            true => format!("🔒 SAFE MODE | {}", message),
+            false => message.to_owned(),
+        };
         let gauge = Gauge::default()
             .block(
                 Block::default()
@@ -128,7 +214,7 @@ pub mod main_ui {
             .style_warn(Style::default().fg(Color::Yellow))
             .style_trace(Style::default().fg(Color::Magenta))
             .style_info(Style::default().fg(Color::Cyan));
-            // .style(Style::default().fg(Color::White).bg(Color::Black))
+        // .style(Style::default().fg(Color::White).bg(Color::Black))
         //     .state(&mut app.states[sel]);
         f.render_widget(tui_w, hchunks[1]);
     }
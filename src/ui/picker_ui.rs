@@ -0,0 +1,76 @@
+#![allow(unused_doc_comments)]
+
+pub mod picker_ui {
+    use std::path::Path;
+
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Corner, Direction, Layout},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, List, ListItem},
+        Frame,
+    };
+
+    use crate::lib::{app::app_mod::StatefulList, picker::picker::EntryStruct};
+
+    pub fn picker_ui<B: Backend>(
+        f: &mut Frame<B>,
+        entries: &StatefulList<EntryStruct>,
+        dir: &Path,
+        message: &str,
+    ) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(15), Constraint::Percentage(85)].as_ref())
+            .split(size);
+
+        /**
+         * ! Top message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded)
+                    .title(format!(" {} ", dir.display()))
+                    .title_alignment(Alignment::Center),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Entries block
+         */
+        let items: Vec<ListItem> = entries
+            .items
+            .iter()
+            .map(|e| match e.is_dir {
+                true => ListItem::new(Spans::from(Span::styled(
+                    format!("{}/", e.name),
+                    Style::default().fg(Color::LightBlue),
+                ))),
+                false => ListItem::new(Spans::from(e.name.clone())),
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Enter open or pick | Backspace up | Esc cancel ")
+                    .title_alignment(Alignment::Center),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::ITALIC),
+            )
+            .highlight_symbol(">> ")
+            .start_corner(Corner::TopLeft);
+        f.render_stateful_widget(list, vchunks[1], &mut entries.state.clone());
+    }
+}
@@ -0,0 +1,99 @@
+#![allow(unused_doc_comments)]
+
+pub mod serve_ui {
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Direction, Layout},
+        style::{Color, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, List, ListItem},
+        Frame,
+    };
+
+    use crate::{
+        lib::{
+            config::config::ServeOptionsStruct, form::form::FormStruct, serve::serve::listen_url,
+        },
+        ui::form_ui::form_ui::form_ui,
+    };
+
+    pub fn serve_ui<B: Backend>(
+        f: &mut Frame<B>,
+        name: &str,
+        form: &FormStruct,
+        serves: &[ServeOptionsStruct],
+        message: &str,
+    ) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(75),
+                    Constraint::Percentage(10),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        let hchunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
+            .split(vchunks[1]);
+
+        /**
+         * ! Top message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Serve options
+         */
+        form_ui(f, hchunks[0], form, &format!(" Serve {}: ", name));
+
+        /**
+         * ! Running serves
+         */
+        let items: Vec<ListItem> = serves
+            .iter()
+            .map(|s| {
+                ListItem::new(Spans::from(vec![
+                    Span::styled(
+                        format!("{} [{}] ", s.name, s.protocol),
+                        Style::default().fg(Color::White),
+                    ),
+                    Span::styled(listen_url(s), Style::default().fg(Color::LightGreen)),
+                ]))
+            })
+            .collect();
+        let list_serves = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" Serving ")
+                .title_alignment(Alignment::Center),
+        );
+        f.render_widget(list_serves, hchunks[1]);
+
+        /**
+         * ! Keys
+         */
+        let gauge = Gauge::default()
+            .label(Span::styled(
+                "Enter start | Delete stop | Left/Right change | Esc back to main",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[2]);
+    }
+}
@@ -0,0 +1,178 @@
+#![allow(unused_doc_comments)]
+
+pub mod sync_ui {
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Corner, Direction, Layout},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, List, ListItem, ListState},
+        Frame,
+    };
+
+    use crate::lib::{
+        config::config::SyncJobStruct,
+        schedule::schedule::{describe, format_time},
+        sync::sync::{PreviewAction, PreviewStruct},
+    };
+
+    pub fn sync_ui<B: Backend>(
+        f: &mut Frame<B>,
+        syncs: &[SyncJobStruct],
+        state: &ListState,
+        preview: Option<&PreviewStruct>,
+        message: &str,
+    ) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(75),
+                    Constraint::Percentage(10),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        let hchunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(45), Constraint::Percentage(55)].as_ref())
+            .split(vchunks[1]);
+
+        /**
+         * ! Top message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Sync jobs
+         */
+        let items: Vec<ListItem> = syncs
+            .iter()
+            .map(|s| {
+                ListItem::new(vec![
+                    Spans::from(vec![
+                        Span::styled(
+                            s.name.clone(),
+                            Style::default().add_modifier(Modifier::BOLD),
+                        ),
+                        Span::styled(
+                            format!(" [{}]", s.mode),
+                            Style::default().fg(match s.mode.as_str() {
+                                "copy" => Color::LightGreen,
+                                _ => Color::Yellow,
+                            }),
+                        ),
+                    ]),
+                    Spans::from(Span::styled(
+                        format!(
+                            "  {} → {}{}",
+                            s.source,
+                            s.destination,
+                            match s.filter.is_empty() {
+                                true => "",
+                                false => " [filtered]",
+                            }
+                        ),
+                        Style::default().fg(Color::DarkGray),
+                    )),
+                    Spans::from(Span::styled(
+                        format!(
+                            "  ⏱ {}{}{}",
+                            describe(s),
+                            match s.condition.as_str() {
+                                "always" | "" => String::new(),
+                                condition => format!(" [{}]", condition),
+                            },
+                            match s.last_run {
+                                Some(last) => format!(" | last {}", format_time(last)),
+                                None => String::new(),
+                            }
+                        ),
+                        Style::default().fg(Color::Gray),
+                    )),
+                ])
+            })
+            .collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Sync jobs ")
+                    .title_alignment(Alignment::Center),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::ITALIC),
+            )
+            .start_corner(Corner::TopLeft);
+        f.render_stateful_widget(list, hchunks[0], &mut state.clone());
+
+        /**
+         * ! Dry run preview
+         */
+        let (title, items): (String, Vec<ListItem>) = match preview {
+            Some(preview) => (
+                format!(
+                    " {}{}: {} new, {} updated, {} deleted ",
+                    preview.name,
+                    match (preview.done, preview.failed) {
+                        (false, _) => " (dry run…)",
+                        (true, true) => " (failed)",
+                        (true, false) => "",
+                    },
+                    preview.count(PreviewAction::Create),
+                    preview.count(PreviewAction::Update),
+                    preview.count(PreviewAction::Delete),
+                ),
+                preview
+                    .entries
+                    .iter()
+                    .map(|e| {
+                        let color = match e.action {
+                            PreviewAction::Create => Color::LightGreen,
+                            PreviewAction::Update => Color::Yellow,
+                            PreviewAction::Delete => Color::LightRed,
+                        };
+                        ListItem::new(Span::styled(
+                            format!("{} {}", e.action.symbol(), e.path),
+                            Style::default().fg(color),
+                        ))
+                    })
+                    .collect(),
+            ),
+            None => (String::from(" Preview "), vec![]),
+        };
+        let list = List::new(items).block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(title)
+                .title_alignment(Alignment::Center),
+        );
+        f.render_widget(list, hchunks[1]);
+
+        /**
+         * ! Keys
+         */
+        let gauge = Gauge::default()
+            .label(Span::styled(
+                "i new | e edit | f filters | x delete | Enter dry run, then 'y' to run | j jobs | Esc back to main",
+                Style::default().fg(Color::DarkGray),
+            ))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[2]);
+    }
+}
@@ -0,0 +1,179 @@
+#![allow(unused_doc_comments)]
+
+pub mod usage_ui {
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Corner, Direction, Layout},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, List, ListItem, ListState},
+        Frame,
+    };
+
+    use crate::lib::{
+        browser::browser::breadcrumb,
+        usage::usage::{UsageEntryStruct, UsageScanStruct, UsageSort},
+        utils::utils::format_size,
+    };
+
+    const BAR_WIDTH: usize = 20;
+
+    /**
+     * `██████░░░░` for a share of the folder
+     */
+    fn bar(ratio: f64) -> String {
+        let full = ((ratio * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+        format!("{}{}", "█".repeat(full), "░".repeat(BAR_WIDTH - full))
+    }
+
+    fn usage_item<'a>(entry: &UsageEntryStruct, total: u64, sort: UsageSort) -> ListItem<'a> {
+        let part = match sort {
+            UsageSort::Size => entry.size,
+            UsageSort::Count => entry.count,
+        };
+        let ratio = match total {
+            0 => 0.0,
+            total => part as f64 / total as f64,
+        };
+        let color = match ratio {
+            r if r >= 0.5 => Color::LightRed,
+            r if r >= 0.2 => Color::Yellow,
+            _ => Color::LightGreen,
+        };
+        let name = match entry.is_dir {
+            true => format!("{}/", entry.name),
+            false => entry.name.clone(),
+        };
+        ListItem::new(Spans::from(vec![
+            Span::raw(format!("{:>10} ", format_size(entry.size))),
+            Span::styled(bar(ratio), Style::default().fg(color)),
+            Span::raw(format!(
+                " {:>5.1}% {:>7} files  ",
+                ratio * 100.0,
+                entry.count
+            )),
+            Span::styled(
+                name,
+                match entry.is_dir {
+                    true => Style::default().add_modifier(Modifier::BOLD),
+                    false => Style::default(),
+                },
+            ),
+        ]))
+    }
+
+    pub fn usage_ui<B: Backend>(
+        f: &mut Frame<B>,
+        scan: &UsageScanStruct,
+        dir: &str,
+        entries: &[UsageEntryStruct],
+        state: &ListState,
+        sort: UsageSort,
+        message: &str,
+    ) {
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints(
+                [
+                    Constraint::Percentage(15),
+                    Constraint::Percentage(70),
+                    Constraint::Percentage(15),
+                ]
+                .as_ref(),
+            )
+            .split(size);
+
+        /**
+         * ! Folder and message
+         */
+        let path = match (scan.root.is_empty(), dir.is_empty()) {
+            (_, true) => scan.root.clone(),
+            (true, false) => dir.to_owned(),
+            (false, false) => format!("{}/{}", scan.root, dir),
+        };
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded)
+                    .title(Span::styled(
+                        format!(" {} ", breadcrumb(&scan.remote, &path)),
+                        Style::default().add_modifier(Modifier::BOLD),
+                    ))
+                    .title_alignment(Alignment::Center),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Entries with their share of the folder
+         */
+        let (dir_size, dir_count) = scan.tree.totals(dir);
+        let total = match sort {
+            UsageSort::Size => dir_size,
+            UsageSort::Count => dir_count,
+        };
+        let items: Vec<ListItem> = entries.iter().map(|e| usage_item(e, total, sort)).collect();
+        let list = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(format!(
+                        " {} in {} files | sorted by {} ",
+                        format_size(dir_size),
+                        dir_count,
+                        sort.label()
+                    ))
+                    .title_alignment(Alignment::Center),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::ITALIC),
+            )
+            .start_corner(Corner::TopLeft);
+        f.render_stateful_widget(list, vchunks[1], &mut state.clone());
+
+        /**
+         * ! Scan progress and keys
+         */
+        let progress = match (scan.done(), scan.failed(), scan.cancelled) {
+            (_, _, true) => format!(
+                "Scan cancelled at {} files, sizes are partial",
+                scan.tree.count
+            ),
+            (false, _, _) => format!(
+                "Scanning … {} files, {} so far | c cancel",
+                scan.tree.count,
+                format_size(scan.tree.size)
+            ),
+            (true, true, _) => String::from("Scan failed, check logs"),
+            (true, false, _) => format!(
+                "{} files, {} in total",
+                scan.tree.count,
+                format_size(scan.tree.size)
+            ),
+        };
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Rounded)
+                    .title(" Enter open | Backspace up | s sort | x delete | r rescan | Esc back ")
+                    .title_alignment(Alignment::Center),
+            )
+            .label(Span::styled(
+                progress,
+                Style::default().fg(match scan.done() {
+                    true => Color::White,
+                    false => Color::Yellow,
+                }),
+            ))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[2]);
+    }
+}
@@ -0,0 +1,133 @@
+#![allow(unused_doc_comments)]
+
+pub mod wizard_ui {
+    use tui::{
+        backend::Backend,
+        layout::{Alignment, Constraint, Corner, Direction, Layout},
+        style::{Color, Modifier, Style},
+        text::{Span, Spans},
+        widgets::{Block, BorderType, Borders, Gauge, List, ListItem, Paragraph, Wrap},
+        Frame,
+    };
+
+    use crate::{
+        lib::{
+            app::app_mod::StatefulList, form::form::FormStruct,
+            providers::providers::ProviderStruct,
+        },
+        ui::form_ui::form_ui::form_screen_ui,
+    };
+
+    pub fn wizard_ui<B: Backend>(
+        f: &mut Frame<B>,
+        providers: &StatefulList<ProviderStruct>,
+        form: &FormStruct,
+        stage: u8,
+        message: &str,
+    ) {
+        let provider = providers
+            .state
+            .selected()
+            .and_then(|i| providers.items.get(i));
+
+        if stage == 1 {
+            let mut help: Vec<String> = match form.selected_field() {
+                Some(field) if !field.help.is_empty() => vec![field.help.clone()],
+                _ => vec![],
+            };
+            help.push(String::from("Fields marked * are required"));
+            help.push(String::from(
+                "Enter create | Left/Right choose | Space toggle | Esc backends",
+            ));
+            let title = match provider {
+                Some(provider) => format!(" New {} remote ", provider.name),
+                None => String::from(" New remote "),
+            };
+            form_screen_ui(f, form, &title, message, &help);
+            return;
+        }
+
+        let size = f.size();
+
+        let vchunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(15), Constraint::Percentage(85)].as_ref())
+            .split(size);
+
+        let hchunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)].as_ref())
+            .split(vchunks[1]);
+
+        /**
+         * ! Top message
+         */
+        let gauge = Gauge::default()
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::LightRed))
+                    .border_type(BorderType::Rounded),
+            )
+            .label(Span::styled(message, Style::default()))
+            .style(Style::default());
+        f.render_widget(gauge, vchunks[0]);
+
+        /**
+         * ! Backends block
+         */
+        let items: Vec<ListItem> = providers
+            .items
+            .iter()
+            .map(|p| ListItem::new(Spans::from(p.name.clone())).style(Style::default()))
+            .collect();
+        let list_providers = List::new(items)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Backends ")
+                    .title_alignment(Alignment::Center),
+            )
+            .highlight_style(
+                Style::default()
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::ITALIC),
+            )
+            .highlight_symbol(">> ")
+            .start_corner(Corner::TopLeft);
+        f.render_stateful_widget(list_providers, hchunks[0], &mut providers.state.clone());
+
+        /**
+         * ! Description block
+         */
+        let description = match provider {
+            Some(provider) => vec![
+                Spans::from(Span::styled(
+                    provider.description.clone(),
+                    Style::default().add_modifier(Modifier::BOLD),
+                )),
+                Spans::from(""),
+                Spans::from(format!(
+                    "{} options, {} required",
+                    provider.options.len(),
+                    provider.options.iter().filter(|o| o.required).count()
+                )),
+                Spans::from(""),
+                Spans::from(Span::styled(
+                    "Enter choose | Esc back to drives",
+                    Style::default().fg(Color::DarkGray),
+                )),
+            ],
+            None => vec![Spans::from("No backend found, is rclone installed?")],
+        };
+        let description = Paragraph::new(description)
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Backend ")
+                    .title_alignment(Alignment::Center),
+            )
+            .wrap(Wrap { trim: false });
+        f.render_widget(description, hchunks[1]);
+    }
+}